@@ -0,0 +1,181 @@
+//! Backups of files Elaine rewrites outside the registry (`.tex`, `.bib`).
+//!
+//! Each backup is a directory `.elaine/backups/<timestamp>-<label>/`
+//! holding copies of the originals plus a `manifest.yaml` recording
+//! where each copy came from.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::state::elaine_dir;
+
+/// Structure of `.elaine/backups/<name>/manifest.yaml`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Command that made the backup (`rename-key`, `purge`)
+    pub label: String,
+    pub files: Vec<BackedUpFile>,
+
+    /// References unpinned alongside, by library ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub unpinned: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackedUpFile {
+    /// Absolute path of the original
+    pub original: PathBuf,
+    /// File name of the copy inside the backup directory
+    pub stored: String,
+}
+
+pub fn backups_dir() -> PathBuf {
+    elaine_dir().join("backups")
+}
+
+/// Copy `files` into a new backup directory and return its path.
+pub fn create_backup(label: &str, files: &[PathBuf]) -> io::Result<PathBuf> {
+    let base = format!("{}-{}", timestamp(), label);
+
+    // Two backups within the same second get `-2`, `-3`, ...
+    let mut dir = backups_dir().join(&base);
+    let mut n = 2;
+    while dir.exists() {
+        dir = backups_dir().join(format!("{}-{}", base, n));
+        n += 1;
+    }
+
+    fs::create_dir_all(&dir)?;
+
+    let mut manifest = Manifest {
+        label: label.to_string(),
+        files: Vec::new(),
+        unpinned: BTreeMap::new(),
+    };
+
+    for (i, file) in files.iter().enumerate() {
+        let name = file
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "file".to_string());
+        let stored = format!("{:03}-{}", i + 1, name);
+
+        fs::copy(file, dir.join(&stored))?;
+
+        manifest.files.push(BackedUpFile {
+            original: file.canonicalize()?,
+            stored,
+        });
+    }
+
+    save_manifest(&dir, &manifest)?;
+
+    Ok(dir)
+}
+
+pub fn load_manifest(dir: &Path) -> io::Result<Manifest> {
+    let contents = fs::read_to_string(dir.join("manifest.yaml"))?;
+    serde_yaml::from_str(&contents).map_err(io::Error::other)
+}
+
+pub fn save_manifest(dir: &Path, manifest: &Manifest) -> io::Result<()> {
+    let yaml = serde_yaml::to_string(manifest).map_err(io::Error::other)?;
+    fs::write(dir.join("manifest.yaml"), yaml)
+}
+
+/// Backup directory names, oldest first
+pub fn list_backups() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(backups_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().join("manifest.yaml").exists())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort();
+    names
+}
+
+/// A backup by path, by name, or by a unique name prefix (`20250301`).
+/// `latest` is the most recent one.
+pub fn find_backup(selector: &str) -> Option<PathBuf> {
+    let path = Path::new(selector);
+    if path.join("manifest.yaml").exists() {
+        return Some(path.to_path_buf());
+    }
+
+    let names = list_backups();
+
+    if selector == "latest" {
+        return names.last().map(|n| backups_dir().join(n));
+    }
+
+    if names.iter().any(|n| n == selector) {
+        return Some(backups_dir().join(selector));
+    }
+
+    let matches: Vec<&String> = names.iter().filter(|n| n.starts_with(selector)).collect();
+    match matches.as_slice() {
+        [one] => Some(backups_dir().join(one)),
+        _ => None,
+    }
+}
+
+/// Replace `path` with `contents` without ever leaving it half-written:
+/// the new contents go to a sibling temp file that is renamed over it.
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp = path.with_file_name(format!(".{}.eln-tmp", name));
+
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp);
+    })
+}
+
+/// UTC `YYYYMMDD-HHMMSS`
+fn timestamp() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let (days, rem) = ((secs / 86_400) as i64, secs % 86_400);
+    let (y, m, d) = civil_from_days(days);
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        y,
+        m,
+        d,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+/// Days since 1970-01-01 → (year, month, day), proleptic Gregorian
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + i64::from(m <= 2);
+
+    (y, m, d)
+}
@@ -0,0 +1,517 @@
+//! LaTeX ↔ Unicode for field values.
+//!
+//! Registries store clean Unicode; BibTeX input is decoded on import and
+//! re-escaped when printing. Only what can be mapped back is touched:
+//! accented letters (`{\"o}`, `\'{e}`, `\c c`), special letters (`{\ss}`,
+//! `\o`), escaped specials (`\&`, `\%`, `\#`, `\_`), dashes, quotes and
+//! ties. Math (`$...$`), unknown commands, case-protecting braces such
+//! as `{DNA}` and the arguments of `\url`, `\href`, `\path` and `\verb`
+//! are kept verbatim.
+
+/// Accent command, base letters, accented letters (position-aligned)
+const ACCENTS: [(char, &str, &str); 13] = [
+    ('"', "aeiouyAEIOUY", "äëïöüÿÄËÏÖÜŸ"),
+    ('\'', "aeiouycnszAEIOUYCNSZ", "áéíóúýćńśźÁÉÍÓÚÝĆŃŚŹ"),
+    ('`', "aeiouAEIOU", "àèìòùÀÈÌÒÙ"),
+    ('^', "aeiouAEIOU", "âêîôûÂÊÎÔÛ"),
+    ('~', "anoANO", "ãñõÃÑÕ"),
+    ('=', "aeiouAEIOU", "āēīōūĀĒĪŌŪ"),
+    ('.', "zeZEI", "żėŻĖİ"),
+    ('u', "agAG", "ăğĂĞ"),
+    ('v', "csznreCSZNRE", "čšžňřěČŠŽŇŘĚ"),
+    ('H', "ouOU", "őűŐŰ"),
+    ('c', "csCS", "çşÇŞ"),
+    ('k', "aeAE", "ąęĄĘ"),
+    ('r', "auAU", "åůÅŮ"),
+];
+
+/// Letter commands: name, letter, ASCII fold
+const LETTERS: [(&str, char, &str); 16] = [
+    ("ss", 'ß', "ss"),
+    ("o", 'ø', "o"),
+    ("O", 'Ø', "O"),
+    ("ae", 'æ', "ae"),
+    ("AE", 'Æ', "AE"),
+    ("oe", 'œ', "oe"),
+    ("OE", 'Œ', "OE"),
+    ("aa", 'å', "a"),
+    ("AA", 'Å', "A"),
+    ("l", 'ł', "l"),
+    ("L", 'Ł', "L"),
+    ("i", 'ı', "i"),
+    ("dh", 'ð', "d"),
+    ("DH", 'Ð', "D"),
+    ("th", 'þ', "th"),
+    ("TH", 'Þ', "TH"),
+];
+
+/// Characters that are syntax in LaTeX and must be backslash-escaped
+const SPECIALS: [char; 4] = ['&', '%', '#', '_'];
+
+/// Commands whose (first) argument is a URL or path, never decoded or escaped
+const VERBATIM_COMMANDS: [&str; 4] = ["url", "href", "path", "verb"];
+
+/// CSL's equivalent of a case-protecting brace group
+const NOCASE_OPEN: &str = r#"<span class="nocase">"#;
+
+/// Fields holding identifiers or paths, never decoded or escaped
+const VERBATIM_FIELDS: [&str; 4] = ["doi", "url", "eprint", "file"];
+
+/// How `eln printed` writes non-ASCII text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Escape {
+    /// Pure ASCII for classic BibTeX / pdflatex (`{\"o}`, `--`)
+    Latex,
+    /// Unicode as stored, for biber; only LaTeX specials are escaped
+    Utf8,
+}
+
+impl Escape {
+    pub fn parse(s: &str) -> Escape {
+        match s {
+            "utf8" => Escape::Utf8,
+            _ => Escape::Latex,
+        }
+    }
+}
+
+pub fn is_verbatim(field: &str) -> bool {
+    VERBATIM_FIELDS.contains(&field.to_lowercase().as_str())
+}
+
+/// LaTeX → Unicode.
+pub fn decode(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = String::with_capacity(s.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            // Math is copied through untouched
+            '$' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&c| c == '$')
+                    .map(|p| i + 1 + p)
+                    .unwrap_or(chars.len() - 1);
+                out.extend(&chars[i..=end]);
+                i = end + 1;
+            }
+            // `{\"o}` → `ö`; any other group keeps its braces
+            '{' => match command(&chars[i + 1..]) {
+                Some((c, len)) if chars.get(i + 1 + len) == Some(&'}') => {
+                    out.push(c);
+                    i += len + 2;
+                }
+                _ => {
+                    out.push('{');
+                    i += 1;
+                }
+            },
+            '\\' => match command(&chars[i..]) {
+                // `\url{a~b}` is copied as is
+                _ if let Some(len) = verbatim_command_len(&chars[i..]) => {
+                    out.extend(&chars[i..i + len]);
+                    i += len;
+                }
+                Some((c, len)) => {
+                    out.push(c);
+                    i += len;
+                }
+                None => {
+                    // Unknown command: copy its name so `\-` or `\\`
+                    // are not read as anything else
+                    let len = command_name_len(&chars[i..]);
+                    out.extend(&chars[i..i + len]);
+                    i += len;
+                }
+            },
+            '-' => {
+                let run = chars[i..].iter().take_while(|&&c| c == '-').count();
+                match run {
+                    2 => out.push('–'),
+                    3 => out.push('—'),
+                    n => out.extend(std::iter::repeat_n('-', n)),
+                }
+                i += run;
+            }
+            '`' if chars.get(i + 1) == Some(&'`') => {
+                out.push('“');
+                i += 2;
+            }
+            '\'' if chars.get(i + 1) == Some(&'\'') => {
+                out.push('”');
+                i += 2;
+            }
+            '~' => {
+                out.push('\u{a0}');
+                i += 1;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+/// Unicode → LaTeX. Existing escapes (`\&`, `{\"o}`) and math pass
+/// through unchanged, so partially escaped values are safe.
+pub fn encode(s: &str, escape: Escape) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = String::with_capacity(s.len());
+    let mut math = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        i += 1;
+
+        if c == '$' {
+            math = !math;
+            out.push(c);
+            continue;
+        }
+
+        if math {
+            out.push(c);
+            continue;
+        }
+
+        match c {
+            '\\' => {
+                // `\url{a_b}` stays as is; other commands keep their next char
+                let len = verbatim_command_len(&chars[i - 1..]).unwrap_or(2);
+                let end = (i - 1 + len).min(chars.len());
+                out.extend(&chars[i - 1..end]);
+                i = end;
+            }
+            c if SPECIALS.contains(&c) => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\u{a0}' => out.push('~'),
+            c if escape == Escape::Latex && !c.is_ascii() => match encode_char(c) {
+                Some(latex) => out.push_str(&latex),
+                None => out.push(c),
+            },
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// `Müller` → `Muller`, `Straße` → `Strasse`. Characters without a
+/// mapping are kept.
+pub fn ascii_fold(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        if c.is_ascii() {
+            out.push(c);
+        } else if let Some((_, _, fold)) = LETTERS.iter().find(|(_, l, _)| *l == c) {
+            out.push_str(fold);
+        } else if let Some((_, base)) = find_accented(c) {
+            out.push(base);
+        } else {
+            out.push(c);
+        }
+    }
+
+    out
+}
+
+/// `The {DNA} of {Rust}` → `The DNA of Rust`, for formats without case
+/// protection. Command arguments, math and `\{` are kept.
+pub fn strip_case_braces(s: &str) -> String {
+    map_case_braces(s, "", "")
+}
+
+/// `{DNA}` → `<span class="nocase">DNA</span>`, CSL's case protection.
+pub fn case_braces_to_nocase(s: &str) -> String {
+    map_case_braces(s, NOCASE_OPEN, "</span>")
+}
+
+/// `<span class="nocase">DNA</span>` → `{DNA}`. Other markup is kept.
+pub fn nocase_to_case_braces(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    // One entry per open `<span`: true for `nocase`
+    let mut spans: Vec<bool> = Vec::new();
+    let mut rest = s;
+
+    while let Some(pos) = rest.find('<') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        if let Some(after) = rest.strip_prefix(NOCASE_OPEN) {
+            spans.push(true);
+            out.push('{');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("</span>") {
+            out.push_str(if spans.pop() == Some(true) { "}" } else { "</span>" });
+            rest = after;
+        } else {
+            if rest.starts_with("<span") {
+                spans.push(false);
+            }
+            out.push('<');
+            rest = &rest[1..];
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+// --- Helpers ----------------------------------------------------------------
+
+/// Replace the braces of case-protecting groups with `open` / `close`.
+/// Groups that are command arguments (`\emph{x}`) or math stay as is.
+fn map_case_braces(s: &str, open: &str, close: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = String::with_capacity(s.len());
+    // One entry per open brace: true if it protects case
+    let mut groups: Vec<bool> = Vec::new();
+    let mut math = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        match c {
+            '$' => {
+                math = !math;
+                out.push(c);
+            }
+            _ if math => out.push(c),
+            '\\' => {
+                let len = verbatim_command_len(&chars[i..])
+                    .unwrap_or_else(|| command_name_len(&chars[i..]));
+                let end = (i + len).min(chars.len());
+                out.extend(&chars[i..end]);
+                i = end;
+
+                if chars.get(i) == Some(&'{') {
+                    groups.push(false);
+                    out.push('{');
+                    i += 1;
+                }
+                continue;
+            }
+            '{' => {
+                groups.push(true);
+                out.push_str(open);
+            }
+            '}' => match groups.pop() {
+                Some(true) => out.push_str(close),
+                _ => out.push('}'),
+            },
+            c => out.push(c),
+        }
+
+        i += 1;
+    }
+
+    out
+}
+
+/// Parse an accent or letter command at the start of `chars`
+/// (`\"o`, `\"{o}`, `\'\i`, `\c c`, `\ss`, `\&`).
+/// Returns the character and the number of chars consumed.
+fn command(chars: &[char]) -> Option<(char, usize)> {
+    if chars.first() != Some(&'\\') {
+        return None;
+    }
+    let next = *chars.get(1)?;
+
+    if SPECIALS.contains(&next) {
+        return Some((next, 2));
+    }
+
+    // Symbol accents: `\"o`, `\'{e}`
+    if "\"'`^~=.".contains(next) {
+        let (base, len) = accent_argument(&chars[2..], false)?;
+        return Some((accented(next, base)?, 2 + len));
+    }
+
+    let name_len = command_name_len(chars) - 1;
+    let name: String = chars[1..=name_len].iter().collect();
+
+    // Letter accents: `\c c`, `\v{s}`
+    if name_len == 1
+        && "uvHckr".contains(next)
+        && let Some((base, len)) = accent_argument(&chars[2..], true)
+    {
+        return Some((accented(next, base)?, 2 + len));
+    }
+
+    let (_, letter, _) = LETTERS.iter().find(|(n, _, _)| *n == name)?;
+    let mut len = 1 + name_len;
+
+    // TeX eats the space or empty group ending a control word
+    if chars.get(len) == Some(&' ') {
+        len += 1;
+    } else if chars.get(len) == Some(&'{') && chars.get(len + 1) == Some(&'}') {
+        len += 2;
+    }
+
+    Some((*letter, len))
+}
+
+/// Length of `\name` or `\x` at the start of `chars`.
+fn command_name_len(chars: &[char]) -> usize {
+    match chars.get(1) {
+        Some(c) if c.is_ascii_alphabetic() => {
+            1 + chars[1..].iter().take_while(|c| c.is_ascii_alphabetic()).count()
+        }
+        Some(_) => 2,
+        None => 1,
+    }
+}
+
+/// Length of `\url{...}`, `\href{...}`, `\path|...|` or `\verb*|...|`
+/// (command and verbatim argument) at the start of `chars`.
+fn verbatim_command_len(chars: &[char]) -> Option<usize> {
+    let name_len = command_name_len(chars);
+    let name: String = chars[1..name_len].iter().collect();
+
+    if !VERBATIM_COMMANDS.contains(&name.as_str()) {
+        return None;
+    }
+
+    let mut i = name_len;
+    if name == "verb" && chars.get(i) == Some(&'*') {
+        i += 1;
+    }
+
+    match chars.get(i) {
+        // Braced: nested braces balance
+        Some('{') => {
+            let mut depth = 0usize;
+            for (n, &c) in chars[i..].iter().enumerate() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + n + 1);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Some(chars.len())
+        }
+        // Delimited: `\verb|...|`, `\url+...+`
+        Some(&delim) if !delim.is_alphanumeric() && !delim.is_whitespace() => {
+            let close = chars[i + 1..].iter().position(|&c| c == delim);
+            Some(close.map(|p| i + p + 2).unwrap_or(chars.len()))
+        }
+        _ => Some(name_len),
+    }
+}
+
+/// The letter an accent applies to: `o`, `{o}`, `\i` or `{\i}`.
+/// Letter accents need a space or brace before a bare letter.
+fn accent_argument(chars: &[char], letter_accent: bool) -> Option<(char, usize)> {
+    let dotless = |c: &[char]| {
+        c.first() == Some(&'\\')
+            && matches!(c.get(1), Some('i' | 'j'))
+            && !c.get(2).is_some_and(|n| n.is_ascii_alphabetic())
+    };
+
+    match chars.first()? {
+        '{' => {
+            if dotless(&chars[1..]) && chars.get(3) == Some(&'}') {
+                return Some((chars[2], 4));
+            }
+            match (chars.get(1), chars.get(2)) {
+                (Some(c), Some('}')) if c.is_ascii_alphabetic() => Some((*c, 3)),
+                _ => None,
+            }
+        }
+        ' ' if letter_accent => {
+            let (c, len) = accent_argument(&chars[1..], false)?;
+            Some((c, len + 1))
+        }
+        _ if dotless(chars) => Some((chars[1], 2)),
+        c if c.is_ascii_alphabetic() && !letter_accent => Some((*c, 1)),
+        _ => None,
+    }
+}
+
+fn accented(accent: char, base: char) -> Option<char> {
+    let (_, bases, letters) = ACCENTS.iter().find(|(a, _, _)| *a == accent)?;
+    let pos = bases.chars().position(|b| b == base)?;
+    letters.chars().nth(pos)
+}
+
+/// `ö` → (`"`, `o`)
+fn find_accented(c: char) -> Option<(char, char)> {
+    ACCENTS.iter().find_map(|(accent, bases, letters)| {
+        let pos = letters.chars().position(|l| l == c)?;
+        Some((*accent, bases.chars().nth(pos)?))
+    })
+}
+
+fn encode_char(c: char) -> Option<String> {
+    if let Some((name, _, _)) = LETTERS.iter().find(|(_, l, _)| *l == c) {
+        return Some(format!("{{\\{}}}", name));
+    }
+
+    if let Some((accent, base)) = find_accented(c) {
+        return Some(if accent.is_ascii_alphabetic() {
+            format!("{{\\{} {}}}", accent, base)
+        } else {
+            format!("{{\\{}{}}}", accent, base)
+        });
+    }
+
+    Some(
+        match c {
+            '–' => "--",
+            '—' => "---",
+            '“' => "``",
+            '”' => "''",
+            '‘' => "`",
+            '’' => "'",
+            '…' => "{\\ldots}",
+            _ => return None,
+        }
+        .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_braces_round_trip_through_nocase() {
+        let title = "The {DNA} of {Rust}";
+        let csl = case_braces_to_nocase(title);
+        assert_eq!(csl, r#"The <span class="nocase">DNA</span> of <span class="nocase">Rust</span>"#);
+        assert_eq!(nocase_to_case_braces(&csl), title);
+    }
+
+    #[test]
+    fn command_arguments_and_math_keep_their_braces() {
+        let title = r"\emph{On} $x^{2}$ and \{sets\}";
+        assert_eq!(case_braces_to_nocase(title), title);
+    }
+
+    #[test]
+    fn strip_case_braces_for_plain_text() {
+        assert_eq!(strip_case_braces("The {DNA} of {{Rust}}"), "The DNA of Rust");
+        assert_eq!(strip_case_braces(r"\url{a} and \{x\}"), r"\url{a} and \{x\}");
+    }
+
+    #[test]
+    fn other_markup_is_kept() {
+        let csl = r#"<i>E. coli</i> <span style="font-variant:small-caps">in</span> vivo"#;
+        assert_eq!(nocase_to_case_braces(csl), csl);
+    }
+}
@@ -0,0 +1,354 @@
+pub mod latex;
+pub mod names;
+pub mod parser;
+
+use std::collections::{BTreeMap, HashMap};
+
+use colored::*;
+use regex::Regex;
+
+use crate::reference::{Reference, RefKind, Identifiers, Venue};
+use crate::utils::id::make_sid;
+use names::parse_names;
+use parser::{parse_blocks, Block, ParseError, Piece, RawEntry};
+
+/// Month macros predefined by every standard BibTeX style.
+const MONTHS: [(&str, &str); 12] = [
+    ("jan", "January"),
+    ("feb", "February"),
+    ("mar", "March"),
+    ("apr", "April"),
+    ("may", "May"),
+    ("jun", "June"),
+    ("jul", "July"),
+    ("aug", "August"),
+    ("sep", "September"),
+    ("oct", "October"),
+    ("nov", "November"),
+    ("dec", "December"),
+];
+
+/// Fields that map onto dedicated `Reference` / `Venue` / `Identifiers`
+/// slots. Everything else is preserved in `Reference.extra`.
+const MAPPED_FIELDS: [&str; 21] = [
+    "title", "author", "editor", "year", "doi", "isbn", "url", "journal",
+    "booktitle", "publisher", "series", "volume", "number", "pages",
+    "address", "location", "school", "institution", "type", "pmid", "pmcid",
+];
+
+/// Result of parsing a BibTeX document.
+///
+/// Entries that fail to parse are reported in `errors` instead of being
+/// silently skipped; every well-formed entry still ends up in `refs`.
+pub struct BibParse {
+    pub refs: Vec<Reference>,
+    /// Expanded `@preamble` contents, in source order
+    pub preambles: Vec<String>,
+    pub errors: Vec<ParseError>,
+}
+
+pub fn parse_bibtex(input: &str) -> BibParse {
+    let (blocks, mut errors) = parse_blocks(input);
+
+    let mut macros: HashMap<String, String> = MONTHS
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+    let mut refs = Vec::new();
+    let mut preambles = Vec::new();
+
+    // Blocks are processed in order: a macro is only visible to the
+    // entries that follow its @string definition (BibTeX semantics)
+    for block in blocks {
+        match block {
+            Block::String { name, value } => {
+                let (expanded, _) = expand(&value, &macros);
+                macros.insert(name, expanded);
+            }
+            Block::Preamble(value) => {
+                let (expanded, _) = expand(&value, &macros);
+                if !expanded.trim().is_empty() {
+                    preambles.push(expanded.trim().to_string());
+                }
+            }
+            Block::Entry(entry) => match entry_to_reference(entry, &macros) {
+                Ok((r, undefined)) => {
+                    errors.extend(undefined);
+                    refs.push(r);
+                }
+                Err(e) => errors.push(e),
+            },
+        }
+    }
+
+    errors.sort_by_key(|e| (e.line, e.column));
+
+    BibParse {
+        refs,
+        preambles,
+        errors,
+    }
+}
+
+/// Pretty-print parse errors (CLI-facing).
+/// `origin` is the file name, or `<stdin>`.
+pub fn print_parse_errors(origin: &str, errors: &[ParseError]) {
+    for e in errors {
+        let key = e
+            .key
+            .as_deref()
+            .map(|k| format!(" [{}]", k))
+            .unwrap_or_default();
+
+        eprintln!(
+            "{} {}{}",
+            format!("⚠️  {}:{}:{}:", origin, e.line, e.column).yellow(),
+            e.message,
+            key.dimmed()
+        );
+    }
+}
+
+/// True if `name` has a dedicated slot and must not be stored in `extra`.
+pub fn is_mapped_field(name: &str) -> bool {
+    MAPPED_FIELDS.contains(&name.to_lowercase().as_str())
+}
+
+/// Map a raw entry onto a `Reference`.
+///
+/// Undefined macros do not reject the entry: they are kept verbatim and
+/// returned as diagnostics alongside it.
+fn entry_to_reference(
+    entry: RawEntry,
+    macros: &HashMap<String, String>,
+) -> Result<(Reference, Vec<ParseError>), ParseError> {
+    let mut diagnostics = Vec::new();
+    let id = entry.key.trim().replace(['/', ':'], "_");
+
+    let mut title: Option<String> = None;
+    let mut authors = Vec::new();
+    let mut editors = Vec::new();
+    let mut year = None;
+    let mut identifiers = Identifiers::default();
+    let mut extra = BTreeMap::new();
+
+    let mut venue = Venue::default();
+
+    for f in &entry.fields {
+        let (raw, undefined) = expand(&f.value, macros);
+
+        for name in undefined {
+            diagnostics.push(ParseError {
+                line: entry.line,
+                column: entry.column,
+                key: Some(entry.key.clone()),
+                message: format!(
+                    "undefined @string macro '{}' in field '{}'",
+                    name, f.name
+                ),
+            });
+        }
+
+        let val = collapse_whitespace(&raw);
+
+        if val.is_empty() {
+            continue;
+        }
+
+        // Registries store Unicode; `pages` keeps its `--` ranges
+        let val = if latex::is_verbatim(&f.name) || f.name == "pages" {
+            val
+        } else {
+            latex::decode(&val)
+        };
+
+        match f.name.as_str() {
+            "title" => title = Some(val),
+            "author" => authors = parse_names(&val),
+            "editor" => editors = parse_names(&val),
+            "year" => match val.parse() {
+                Ok(y) => year = Some(y),
+                // keep unparseable years verbatim so `eln lint` can flag them
+                Err(_) => {
+                    extra.insert(f.name.clone(), val);
+                }
+            },
+            "doi" => identifiers.doi = Some(val),
+            "isbn" => identifiers.isbn = Some(val),
+            "url" => identifiers.url = Some(val),
+            "pmid" => identifiers.pmid = Some(val),
+            "pmcid" => identifiers.pmcid = Some(val),
+            "journal" => venue.journal = Some(val),
+            "booktitle" => venue.booktitle = Some(val),
+            "publisher" => venue.publisher = Some(val),
+            "series" => venue.series = Some(val),
+            "volume" => venue.volume = Some(val),
+            "number" => venue.issue = Some(val),
+            "pages" => venue.pages = Some(val),
+            "address" | "location" => venue.address = Some(val),
+            "school" => venue.school = Some(val),
+            "institution" => venue.institution = Some(val),
+            "type" => venue.entry_type = Some(val),
+            _ => {
+                extra.insert(f.name.clone(), val);
+            }
+        }
+    }
+
+    extract_arxiv(&mut extra, &mut identifiers);
+
+    let title = match title {
+        Some(t) => t,
+        None => {
+            return Err(ParseError {
+                line: entry.line,
+                column: entry.column,
+                key: Some(entry.key),
+                message: "missing title".to_string(),
+            });
+        }
+    };
+
+    let mut kind = RefKind::from_bibtex(&entry.kind).unwrap_or(RefKind::Misc);
+
+    // BibLaTeX `@thesis` carries the degree in `type`
+    if entry.kind == "thesis"
+        && venue
+            .entry_type
+            .as_deref()
+            .map(str::to_lowercase)
+            .is_some_and(|t| t == "mathesis" || t.contains("master"))
+    {
+        kind = RefKind::MastersThesis;
+    }
+
+    let reference = Reference {
+        id,
+        sid: make_sid(),
+        kind,
+        title,
+        authors,
+        editors,
+        year,
+        identifiers,
+        venue: Some(venue),
+        tags: Vec::new(),
+        notes: None,
+        attachments: Vec::new(),
+        extra,
+        aliases: Vec::new(),
+    };
+
+    Ok((reference, diagnostics))
+}
+
+/// `January`, `jan` or `1` → 1
+pub fn month_number(m: &str) -> Option<u8> {
+    let m = m.trim().to_lowercase();
+
+    if let Ok(n) = m.parse::<u8>() {
+        return (1..=12).contains(&n).then_some(n);
+    }
+
+    MONTHS
+        .iter()
+        .position(|(abbr, _)| m.starts_with(abbr))
+        .map(|i| i as u8 + 1)
+}
+
+/// `February` or `feb` → `feb`, the standard macro for that month.
+/// Anything else (`2`, `Feb.`, `February 15`) has no macro.
+pub fn month_macro(m: &str) -> Option<&'static str> {
+    let m = m.trim();
+
+    MONTHS
+        .iter()
+        .find(|(abbr, name)| m.eq_ignore_ascii_case(abbr) || m.eq_ignore_ascii_case(name))
+        .map(|(abbr, _)| *abbr)
+}
+
+/// 3 → `March` (the expansion of the standard `mar` macro)
+pub fn month_name(n: u8) -> Option<&'static str> {
+    MONTHS.get(usize::from(n).checked_sub(1)?).map(|(_, name)| *name)
+}
+
+/// Move arXiv metadata out of `extra` into `Identifiers`.
+///
+/// Accepts `arxivid` / `arxiv`, and `eprint` when `archivePrefix` /
+/// `eprinttype` is arXiv (or absent and the value looks like an arXiv ID).
+/// `eprint`s from other archives stay in `extra` untouched.
+fn extract_arxiv(extra: &mut BTreeMap<String, String>, identifiers: &mut Identifiers) {
+    let archive = extra
+        .get("archiveprefix")
+        .or_else(|| extra.get("eprinttype"))
+        .map(|a| a.to_lowercase());
+
+    let id = match extra.remove("arxivid").or_else(|| extra.remove("arxiv")) {
+        Some(id) => Some(id),
+        None => {
+            let is_arxiv = match (&archive, extra.get("eprint")) {
+                (Some(a), Some(_)) => a == "arxiv",
+                (None, Some(e)) => looks_like_arxiv(e),
+                _ => false,
+            };
+            if is_arxiv { extra.remove("eprint") } else { None }
+        }
+    };
+
+    let Some(id) = id else {
+        return;
+    };
+
+    if archive.as_deref() == Some("arxiv") {
+        extra.remove("archiveprefix");
+        extra.remove("eprinttype");
+    }
+
+    let class = extra
+        .remove("primaryclass")
+        .or_else(|| extra.remove("eprintclass"));
+
+    let id = id.trim();
+    let id = id
+        .strip_prefix("arXiv:")
+        .or_else(|| id.strip_prefix("arxiv:"))
+        .unwrap_or(id);
+
+    identifiers.arxiv = Some(id.to_string());
+    identifiers.arxiv_class = identifiers.arxiv_class.take().or(class);
+}
+
+/// `2101.00001`, `2101.00001v2` or old-style `hep-th/9901001`
+fn looks_like_arxiv(s: &str) -> bool {
+    Regex::new(r"^(arXiv:)?(\d{4}\.\d{4,5}|[a-z\-]+(\.[A-Z]{2})?/\d{7})(v\d+)?$")
+        .unwrap()
+        .is_match(s.trim())
+}
+
+/// Join `#`-concatenated pieces, expanding macros.
+/// Returns the text plus the names of any undefined macros, which are
+/// kept verbatim rather than dropped.
+fn expand(pieces: &[Piece], macros: &HashMap<String, String>) -> (String, Vec<String>) {
+    let mut out = String::new();
+    let mut undefined = Vec::new();
+
+    for p in pieces {
+        match p {
+            Piece::Literal(s) => out.push_str(s),
+            Piece::Macro(name) => match macros.get(name) {
+                Some(v) => out.push_str(v),
+                None => {
+                    out.push_str(name);
+                    undefined.push(name.clone());
+                }
+            },
+        }
+    }
+
+    (out, undefined)
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
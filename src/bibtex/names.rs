@@ -0,0 +1,390 @@
+//! BibTeX personal names.
+//!
+//! Implements the three forms understood by BibTeX:
+//!
+//! - `First von Last`
+//! - `von Last, First`
+//! - `von Last, Jr, First`
+//!
+//! The `von` part is the run of words starting with a lowercase letter;
+//! the last word is always part of `Last`. Braced groups are never split,
+//! so `{World Health Organization}` is kept whole as a corporate name.
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct Name {
+    pub family: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub given: Option<String>,
+
+    /// Particle printed before the family name (`van der`, `de`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub von: Option<String>,
+
+    /// Generational suffix (`Jr.`, `III`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jr: Option<String>,
+
+    /// Corporate / institutional name: `family` holds it whole
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub literal: bool,
+}
+
+impl Name {
+    pub fn literal(name: &str) -> Name {
+        Name {
+            family: name.trim().to_string(),
+            literal: true,
+            ..Name::default()
+        }
+    }
+
+    /// BibTeX form: `von Last, Jr, First`, or `{Literal}`
+    pub fn to_bibtex(&self) -> String {
+        if self.literal {
+            return format!("{{{}}}", self.family);
+        }
+
+        let mut out = match &self.von {
+            Some(von) => format!("{} {}", von, self.family),
+            None => self.family.clone(),
+        };
+
+        if let Some(jr) = &self.jr {
+            out = format!("{}, {}", out, jr);
+        }
+
+        match &self.given {
+            Some(given) => format!("{}, {}", out, given),
+            None if self.jr.is_some() => format!("{}, ", out),
+            None => out,
+        }
+    }
+
+    /// Reading form: `First von Last, Jr`
+    pub fn display(&self) -> String {
+        if self.literal {
+            return self.family.clone();
+        }
+
+        let mut parts: Vec<&str> = Vec::new();
+        parts.extend(self.given.as_deref());
+        parts.extend(self.von.as_deref());
+        parts.push(&self.family);
+
+        let out = strip_braces(&parts.join(" "));
+        match &self.jr {
+            Some(jr) => format!("{}, {}", out, jr),
+            None => out,
+        }
+    }
+
+    /// Family name without braces, as used in citation keys
+    pub fn family_plain(&self) -> String {
+        strip_braces(&self.family)
+    }
+
+    /// Case-insensitive key for bibliography ordering: family, given,
+    /// particle, suffix (biblatex's default `useprefix=false`)
+    pub fn sort_key(&self) -> String {
+        [
+            Some(self.family.as_str()),
+            self.given.as_deref(),
+            self.von.as_deref(),
+            self.jr.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|p| strip_braces(p).to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_bibtex())
+    }
+}
+
+/// Registries written before structured names store plain strings;
+/// both forms are accepted.
+impl<'de> Deserialize<'de> for Name {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Fields {
+            family: String,
+            #[serde(default)]
+            given: Option<String>,
+            #[serde(default)]
+            von: Option<String>,
+            #[serde(default)]
+            jr: Option<String>,
+            #[serde(default)]
+            literal: bool,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Text(String),
+            Fields(Fields),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Text(s) => parse_name(&s),
+            Repr::Fields(f) => Name {
+                family: f.family,
+                given: f.given,
+                von: f.von,
+                jr: f.jr,
+                literal: f.literal,
+            },
+        })
+    }
+}
+
+/// Split an `author` / `editor` field on top-level ` and `.
+pub fn parse_names(field: &str) -> Vec<Name> {
+    let mut names = Vec::new();
+    let mut current = String::new();
+
+    // Braced groups are single words, so `{Barnes and Noble}` survives
+    for word in words(field, false) {
+        if word.eq_ignore_ascii_case("and") && !current.is_empty() {
+            names.push(parse_name(&current));
+            current.clear();
+            continue;
+        }
+
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+
+    if !current.trim().is_empty() {
+        names.push(parse_name(&current));
+    }
+
+    names
+}
+
+/// Inverse of [`parse_names`]: the BibTeX `author` / `editor` value.
+pub fn join_names(names: &[Name]) -> String {
+    names
+        .iter()
+        .map(Name::to_bibtex)
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+/// Parse a single name according to the BibTeX name grammar.
+pub fn parse_name(input: &str) -> Name {
+    let input = input.trim();
+
+    // `{World Health Organization}`: one braced group, never split
+    if is_single_group(input) {
+        return Name::literal(&input[1..input.len() - 1]);
+    }
+
+    let parts: Vec<Vec<String>> = split_commas(input)
+        .iter()
+        .map(|p| words(p, true))
+        .collect();
+
+    match parts.as_slice() {
+        [] => Name::default(),
+        // First von Last
+        [all] => {
+            let Some((last, rest)) = all.split_last() else {
+                return Name::default();
+            };
+
+            match rest.iter().position(|w| is_lowercase_word(w)) {
+                Some(von_start) => {
+                    let von_end = rest
+                        .iter()
+                        .rposition(|w| is_lowercase_word(w))
+                        .unwrap_or(von_start);
+
+                    let mut family: Vec<String> = rest[von_end + 1..].to_vec();
+                    family.push(last.clone());
+
+                    Name {
+                        given: join(&rest[..von_start]),
+                        von: join(&rest[von_start..=von_end]),
+                        family: family.join(" "),
+                        ..Name::default()
+                    }
+                }
+                None => Name {
+                    given: join(rest),
+                    family: last.clone(),
+                    ..Name::default()
+                },
+            }
+        }
+        // von Last, First  /  von Last, Jr, First
+        [von_last, tail @ ..] => {
+            let (von, family) = split_von_last(von_last);
+
+            let (jr, given) = match tail {
+                [given] => (None, join(given)),
+                [jr, given @ ..] => (join(jr), join(&given.concat())),
+                [] => (None, None),
+            };
+
+            Name {
+                given,
+                von,
+                family,
+                jr,
+                ..Name::default()
+            }
+        }
+    }
+}
+
+/// `van der Waals` → (`van der`, `Waals`). The last word always belongs
+/// to the family name, even when lowercase.
+fn split_von_last(words: &[String]) -> (Option<String>, String) {
+    let Some((last, rest)) = words.split_last() else {
+        return (None, String::new());
+    };
+
+    match rest.iter().rposition(|w| is_lowercase_word(w)) {
+        Some(von_end) => {
+            let mut family: Vec<String> = rest[von_end + 1..].to_vec();
+            family.push(last.clone());
+            (join(&rest[..=von_end]), family.join(" "))
+        }
+        None => (None, words.join(" ")),
+    }
+}
+
+/// A word is lowercase (part of `von`) if its first letter at brace
+/// depth 0 is lowercase. Special characters such as `{\"u}` count by
+/// the letter they accent; other braced groups are never lowercase.
+fn is_lowercase_word(word: &str) -> bool {
+    let mut chars = word.chars().peekable();
+    let mut depth = 0usize;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                if depth == 0 && chars.peek() != Some(&'\\') {
+                    return false;
+                }
+                depth += 1;
+            }
+            '}' => depth = depth.saturating_sub(1),
+            '\\' if depth > 0 => {
+                // control word (`\ss`, `\o`) or symbol (`\"`, `\'`)
+                let mut name = String::new();
+                while let Some(&n) = chars.peek() {
+                    if !n.is_ascii_alphabetic() {
+                        break;
+                    }
+                    name.push(n);
+                    chars.next();
+                }
+                if let Some(first) = name.chars().next() {
+                    return first.is_lowercase();
+                }
+                chars.next(); // the accent symbol itself
+            }
+            c if c.is_alphabetic() => return c.is_lowercase(),
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// Split on commas at brace depth 0.
+fn split_commas(s: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut depth = 0usize;
+
+    for c in s.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(String::new());
+                continue;
+            }
+            _ => {}
+        }
+        parts.last_mut().unwrap().push(c);
+    }
+
+    parts
+}
+
+/// Split on whitespace at brace depth 0 (and on `~` ties if `ties`).
+fn words(s: &str, ties: bool) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+
+    for c in s.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            c if depth == 0 && (c.is_whitespace() || (ties && c == '~')) => {
+                if !current.is_empty() {
+                    out.push(std::mem::take(&mut current));
+                }
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        out.push(current);
+    }
+
+    out
+}
+
+fn is_single_group(s: &str) -> bool {
+    if !s.starts_with('{') || !s.ends_with('}') {
+        return false;
+    }
+
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 && i != s.len() - 1 {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    depth == 0
+}
+
+fn join(words: &[String]) -> Option<String> {
+    if words.is_empty() {
+        None
+    } else {
+        Some(words.join(" "))
+    }
+}
+
+fn strip_braces(s: &str) -> String {
+    s.chars().filter(|c| *c != '{' && *c != '}').collect()
+}
@@ -0,0 +1,492 @@
+//! Brace-aware BibTeX grammar.
+//!
+//! Turns raw `.bib` text into [`Block`] values without interpreting
+//! any field. Macro expansion and mapping onto `Reference` live in
+//! `bibtex::parse_bibtex`.
+//!
+//! Supported syntax:
+//! - `@type{key, ...}` and `@type(key, ...)`
+//! - `@string{name = value}`, `@preamble{value}` and `@comment{...}`
+//! - `{...}` values with arbitrary brace nesting
+//! - `"..."` values (braces inside quotes are balanced)
+//! - bare numbers and bare identifiers (macros)
+//! - `#` concatenation
+//! - entries closed on the same line as their last field
+//!
+//! Entries record their byte span, so callers can rewrite a file
+//! while copying everything else verbatim.
+
+use std::ops::Range;
+
+/// A top-level `@...` block, in source order.
+#[derive(Debug)]
+pub enum Block {
+    Entry(RawEntry),
+    /// `@string{name = value}`
+    String {
+        name: String,
+        value: Vec<Piece>,
+    },
+    /// `@preamble{value}`
+    Preamble(Vec<Piece>),
+}
+
+/// One `@type{key, field = value, ...}` block.
+#[derive(Debug)]
+pub struct RawEntry {
+    /// Lowercased entry type (`article`, `book`, ...)
+    pub kind: String,
+    pub key: String,
+    pub fields: Vec<RawField>,
+    pub line: usize,
+    pub column: usize,
+    /// Bytes from the `@` through the closing delimiter
+    pub span: Range<usize>,
+}
+
+#[derive(Debug)]
+pub struct RawField {
+    /// Lowercased field name
+    pub name: String,
+    pub value: Vec<Piece>,
+}
+
+/// A single operand of a (possibly `#`-concatenated) field value.
+#[derive(Debug)]
+pub enum Piece {
+    /// Braced, quoted or numeric text (outer delimiters stripped)
+    Literal(String),
+    /// Bare identifier, to be expanded by the caller
+    Macro(String),
+}
+
+/// A syntax error, anchored at the position where parsing failed.
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// Citation key of the entry being parsed, when already known
+    pub key: Option<String>,
+    pub message: String,
+}
+
+pub fn parse_blocks(input: &str) -> (Vec<Block>, Vec<ParseError>) {
+    let mut cur = Cursor::new(input);
+    let mut blocks = Vec::new();
+    let mut errors = Vec::new();
+
+    loop {
+        // Anything outside an entry is an implicit comment
+        while let Some(c) = cur.peek() {
+            if c == '@' {
+                break;
+            }
+            cur.bump();
+        }
+
+        if cur.at_end() {
+            break;
+        }
+
+        let start = cur.pos;
+
+        match parse_block(&mut cur) {
+            Ok(Some(block)) => blocks.push(block),
+            Ok(None) => {}
+            Err(e) => {
+                errors.push(e);
+                cur.recover(start);
+            }
+        }
+    }
+
+    (blocks, errors)
+}
+
+// ============================================================
+// GRAMMAR
+// ============================================================
+
+/// Returns `None` for `@comment` blocks.
+fn parse_block(cur: &mut Cursor) -> Result<Option<Block>, ParseError> {
+    let (line, column, start) = (cur.line, cur.column, cur.byte);
+    cur.bump(); // '@'
+    cur.skip_ws();
+
+    let kind = cur.take_ident().to_lowercase();
+    if kind.is_empty() {
+        return Err(cur.error(None, "expected entry type after '@'"));
+    }
+
+    cur.skip_ws();
+
+    if kind == "comment" {
+        // `@comment{...}` is skipped as a balanced block; a bare
+        // `@comment` only comments out itself (BibTeX semantics)
+        if let Some(open @ ('{' | '(')) = cur.peek() {
+            cur.bump();
+            let close = if open == '{' { '}' } else { ')' };
+            if cur.take_balanced(close).is_none() {
+                return Err(error_at(line, column, None, "unterminated @comment"));
+            }
+        }
+        return Ok(None);
+    }
+
+    let close = match cur.peek() {
+        Some('{') => '}',
+        Some('(') => ')',
+        _ => {
+            return Err(cur.error(
+                None,
+                &format!("expected '{{' or '(' after '@{}'", kind),
+            ))
+        }
+    };
+    cur.bump();
+    cur.skip_ws();
+
+    match kind.as_str() {
+        "string" => parse_string(cur, close).map(Some),
+        "preamble" => parse_preamble(cur, close).map(Some),
+        _ => parse_entry(cur, kind, close, (line, column, start))
+            .map(|e| Some(Block::Entry(e))),
+    }
+}
+
+/// `@string{name = value}` (opening delimiter already consumed)
+fn parse_string(cur: &mut Cursor, close: char) -> Result<Block, ParseError> {
+    let name = cur.take_ident().to_lowercase();
+    if name.is_empty() {
+        return Err(cur.error(None, "expected macro name in @string"));
+    }
+
+    cur.skip_ws();
+    if cur.peek() != Some('=') {
+        return Err(cur.error(
+            None,
+            &format!("expected '=' after @string name '{}'", name),
+        ));
+    }
+    cur.bump();
+
+    let value = parse_value(cur, &name)?;
+    expect_close(cur, close)?;
+
+    Ok(Block::String { name, value })
+}
+
+/// `@preamble{value}` (opening delimiter already consumed)
+fn parse_preamble(cur: &mut Cursor, close: char) -> Result<Block, ParseError> {
+    let value = parse_value(cur, "@preamble")?;
+    expect_close(cur, close)?;
+
+    Ok(Block::Preamble(value))
+}
+
+fn expect_close(cur: &mut Cursor, close: char) -> Result<(), ParseError> {
+    cur.skip_ws();
+    if cur.peek() != Some(close) {
+        return Err(cur.error(None, &format!("expected '{}'", close)));
+    }
+    cur.bump();
+    Ok(())
+}
+
+fn parse_entry(
+    cur: &mut Cursor,
+    kind: String,
+    close: char,
+    (line, column, start): (usize, usize, usize),
+) -> Result<RawEntry, ParseError> {
+    let key = parse_key(cur, close)?;
+    let mut fields = Vec::new();
+
+    loop {
+        cur.skip_ws();
+
+        match cur.peek() {
+            Some(c) if c == close => {
+                cur.bump();
+                break;
+            }
+            Some(',') => {
+                cur.bump();
+                continue;
+            }
+            Some('@') => {
+                return Err(cur.error(
+                    Some(&key),
+                    &format!("unterminated entry (missing '{}')", close),
+                ))
+            }
+            None => {
+                return Err(cur.error(
+                    Some(&key),
+                    &format!("unexpected end of input (missing '{}')", close),
+                ))
+            }
+            _ => {}
+        }
+
+        let name = cur.take_ident().to_lowercase();
+        if name.is_empty() {
+            return Err(cur.error(Some(&key), "expected field name"));
+        }
+
+        cur.skip_ws();
+        if cur.peek() != Some('=') {
+            return Err(cur.error(
+                Some(&key),
+                &format!("expected '=' after field '{}'", name),
+            ));
+        }
+        cur.bump();
+
+        let value = parse_value(cur, &key)?;
+
+        cur.skip_ws();
+        match cur.peek() {
+            Some(',') => {
+                cur.bump();
+            }
+            Some(c) if c == close => {}
+            _ => {
+                return Err(cur.error(
+                    Some(&key),
+                    &format!("expected ',' or '{}' after field '{}'", close, name),
+                ))
+            }
+        }
+
+        // BibTeX keeps the first occurrence of a duplicated field
+        if !fields.iter().any(|f: &RawField| f.name == name) {
+            fields.push(RawField { name, value });
+        }
+    }
+
+    Ok(RawEntry {
+        kind,
+        key,
+        fields,
+        line,
+        column,
+        span: start..cur.byte,
+    })
+}
+
+fn parse_key(cur: &mut Cursor, close: char) -> Result<String, ParseError> {
+    let mut key = String::new();
+
+    while let Some(c) = cur.peek() {
+        if c == ',' || c == close {
+            break;
+        }
+        if c == '\n' || c == '{' || c == '}' || c == '=' {
+            return Err(cur.error(None, "expected ',' after citation key"));
+        }
+        key.push(c);
+        cur.bump();
+    }
+
+    let key = key.trim().to_string();
+
+    if key.is_empty() {
+        return Err(cur.error(None, "missing citation key"));
+    }
+
+    if key.chars().any(char::is_whitespace) {
+        return Err(cur.error(
+            None,
+            &format!("citation key '{}' contains whitespace", key),
+        ));
+    }
+
+    Ok(key)
+}
+
+/// value := piece ( '#' piece )*
+fn parse_value(cur: &mut Cursor, key: &str) -> Result<Vec<Piece>, ParseError> {
+    let mut pieces = Vec::new();
+
+    loop {
+        cur.skip_ws();
+        pieces.push(parse_piece(cur, key)?);
+        cur.skip_ws();
+
+        if cur.peek() == Some('#') {
+            cur.bump();
+        } else {
+            break;
+        }
+    }
+
+    Ok(pieces)
+}
+
+fn parse_piece(cur: &mut Cursor, key: &str) -> Result<Piece, ParseError> {
+    let (line, column) = (cur.line, cur.column);
+
+    match cur.peek() {
+        Some('{') => {
+            cur.bump();
+            cur.take_balanced('}').map(Piece::Literal).ok_or_else(|| {
+                error_at(line, column, Some(key), "unbalanced braces in value")
+            })
+        }
+        Some('"') => {
+            cur.bump();
+            cur.take_balanced('"').map(Piece::Literal).ok_or_else(|| {
+                error_at(line, column, Some(key), "unterminated quoted value")
+            })
+        }
+        Some(c) if c.is_ascii_digit() => {
+            let mut n = String::new();
+            while let Some(d) = cur.peek().filter(|d| d.is_ascii_digit()) {
+                n.push(d);
+                cur.bump();
+            }
+            Ok(Piece::Literal(n))
+        }
+        _ => {
+            let name = cur.take_ident();
+            if name.is_empty() {
+                Err(cur.error(Some(key), "expected field value"))
+            } else {
+                Ok(Piece::Macro(name.to_lowercase()))
+            }
+        }
+    }
+}
+
+fn error_at(line: usize, column: usize, key: Option<&str>, message: &str) -> ParseError {
+    ParseError {
+        line,
+        column,
+        key: key.map(str::to_string),
+        message: message.to_string(),
+    }
+}
+
+// ============================================================
+// CURSOR
+// ============================================================
+
+struct Cursor {
+    chars: Vec<char>,
+    pos: usize,
+    /// Byte offset of `pos` in the input
+    byte: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Cursor {
+    fn new(input: &str) -> Self {
+        Self {
+            chars: input.chars().collect(),
+            pos: 0,
+            byte: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.chars.len()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += 1;
+            self.byte += c.len_utf8();
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    /// Identifier characters accepted by BibTeX for types, fields and macros.
+    fn take_ident(&mut self) -> String {
+        let mut out = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || "_-:./+'!?&*;<>[]`|".contains(c) {
+                out.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        out
+    }
+
+    /// Read up to `end` at brace depth zero, consuming the terminator.
+    /// The opening delimiter must already be consumed.
+    ///
+    /// An `@` at the start of a line is treated as the start of the next
+    /// entry, so one unbalanced value cannot swallow the rest of the file.
+    fn take_balanced(&mut self, end: char) -> Option<String> {
+        let mut out = String::new();
+        let mut depth = 0usize;
+
+        while let Some(c) = self.peek() {
+            match c {
+                '@' if self.at_line_start() => return None,
+                '{' => depth += 1,
+                c if c == end && depth == 0 => {
+                    self.bump();
+                    return Some(out);
+                }
+                // stray closing brace inside a quoted or parenthesised value
+                '}' if depth == 0 => return None,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            out.push(c);
+            self.bump();
+        }
+
+        None
+    }
+
+    /// True when only indentation precedes the cursor on its line.
+    fn at_line_start(&self) -> bool {
+        self.chars[..self.pos]
+            .iter()
+            .rev()
+            .take_while(|c| **c != '\n')
+            .all(|c| c.is_whitespace())
+    }
+
+    /// Skip to the next `@` that starts a line (ignoring indentation).
+    /// `start` is where the failed entry began, so it is never re-parsed.
+    fn recover(&mut self, start: usize) {
+        if self.pos == start {
+            self.bump();
+        }
+
+        while let Some(c) = self.peek() {
+            if c == '@' && self.at_line_start() {
+                return;
+            }
+            self.bump();
+        }
+    }
+
+    fn error(&self, key: Option<&str>, message: &str) -> ParseError {
+        error_at(self.line, self.column, key, message)
+    }
+}
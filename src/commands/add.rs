@@ -1,11 +1,17 @@
+use std::collections::BTreeMap;
 use std::io::{self, Read};
 use colored::*;
-use crate::bibtex::parse_bibtex;
+use crate::bibtex::{parse_bibtex, print_parse_errors};
+use crate::bibtex::names::parse_names;
+use crate::csl::parse_csl_json;
+use crate::nbib::parse_nbib;
+use crate::ris::parse_ris;
 use crate::reference_store::create_or_update_ref;
 use crate::state::load_index;
 use crate::project_store::{load_project, save_project};
 use crate::reference::{Reference, RefKind, Identifiers, Venue};
-use crate::utils::id::{make_ref_id, make_sid};
+use crate::utils::citekey::make_key;
+use crate::utils::id::make_sid;
 
 pub fn run_add(interactive: bool, args: Vec<String>) {
     if interactive {
@@ -13,8 +19,8 @@ pub fn run_add(interactive: bool, args: Vec<String>) {
         return;
     }
 
-    if !args.is_empty() && args.iter().all(|a| is_bib_file(a)) {
-        run_add_bib_files(args);
+    if !args.is_empty() && args.iter().all(|a| is_import_file(a)) {
+        run_add_files(args);
         return;
     }
 
@@ -35,62 +41,63 @@ fn run_add_interactive() {
     let title = prompt_required("Title");
 
     let authors_raw = prompt_required("Authors (use 'and' between names)");
-    let authors: Vec<String> = authors_raw
-        .split(" and ")
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect();
+    let authors = parse_names(&authors_raw);
 
     let year = prompt_optional("Year")
         .and_then(|y| y.parse::<u16>().ok());
 
     // --- Kind ---
-    let kind = match prompt_optional("Kind [Article]")
-        .as_deref()
-        .unwrap_or("Article")
-        .to_lowercase()
-        .as_str()
-    {
-        "inproceedings" => RefKind::InProceedings,
-        "incollection" => RefKind::InCollection,
-        "inbook" => RefKind::InBook,
-        "book" => RefKind::Book,
-        "misc" => RefKind::Misc,
-        _ => RefKind::Article,
-    };
+    let kind = prompt_optional("Kind [Article]")
+        .and_then(|k| RefKind::from_bibtex(&k))
+        .unwrap_or(RefKind::Article);
 
     // --- Editors ---
     let editors = prompt_optional("Editors (use 'and')")
-        .map(|s| {
-            s.split(" and ")
-                .map(|x| x.trim().to_string())
-                .filter(|x| !x.is_empty())
-                .collect()
-        })
-        .unwrap_or_else(Vec::new);
+        .map(|s| parse_names(&s))
+        .unwrap_or_default();
 
     println!("{}", "--- Venue ---".dimmed());
 
-    let venue = Venue {
+    let mut venue = Venue {
         journal: prompt_optional("Journal"),
         booktitle: prompt_optional("Booktitle"),
         publisher: prompt_optional("Publisher"),
         series: prompt_optional("Series"),
         volume: prompt_optional("Volume"),
-        issue: prompt_optional("Issue"),
+        issue: prompt_optional("Issue / Number"),
         pages: prompt_optional("Pages"),
         address: prompt_optional("Address"),
+        ..Venue::default()
     };
 
+    // --- Type-specific fields ---
+    if kind.is_thesis() {
+        venue.school = prompt_optional("School");
+    }
+    if kind == RefKind::TechReport {
+        venue.institution = prompt_optional("Institution");
+    }
+    if kind.is_thesis() || kind == RefKind::TechReport {
+        venue.entry_type = prompt_optional("Type");
+    }
+
     println!("{}", "--- Identifiers ---".dimmed());
 
-    let identifiers = Identifiers {
+    let mut identifiers = Identifiers {
         doi: prompt_optional("DOI"),
         url: prompt_optional("URL"),
         isbn: prompt_optional("ISBN"),
         arxiv: prompt_optional("arXiv"),
+        ..Identifiers::default()
     };
 
+    if identifiers.arxiv.is_some() {
+        identifiers.arxiv_class = prompt_optional("arXiv class");
+    }
+
+    identifiers.pmid = prompt_optional("PMID");
+    identifiers.pmcid = prompt_optional("PMCID");
+
     let tags = prompt_optional("Tags (comma-separated)")
         .map(|s| {
             s.split(',')
@@ -98,15 +105,13 @@ fn run_add_interactive() {
                 .filter(|x| !x.is_empty())
                 .collect()
         })
-        .unwrap_or_else(Vec::new);
+        .unwrap_or_default();
 
     let notes = prompt_optional("Notes");
 
     // --- Build reference ---
-    let id = make_ref_id(&authors, year, &title);
-
-    let reference = Reference {
-        id: id.clone(),
+    let mut reference = Reference {
+        id: String::new(),
         sid: make_sid(),
         kind,
         title,
@@ -118,9 +123,13 @@ fn run_add_interactive() {
         tags,
         notes,
         attachments: Vec::new(),
+        extra: BTreeMap::new(),
+        aliases: Vec::new(),
     };
 
-    create_or_update_ref(reference);
+    reference.id = make_key(&reference);
+
+    let id = create_or_update_ref(reference);
     attach_to_active_project(&id);
 
     println!(
@@ -132,16 +141,15 @@ fn run_add_interactive() {
 }
 
 
-fn run_add_bib_files(paths: Vec<String>) {
-    let mut combined = String::new();
+/// Import `.bib`, CSL `.json`, `.ris` and PubMed `.nbib` files,
+/// dispatched by extension.
+fn run_add_files(paths: Vec<String>) {
+    let mut refs = Vec::new();
+    let mut preambles = Vec::new();
 
     for p in &paths {
-        match std::fs::read_to_string(p) {
-            Ok(s) => {
-                let normalized = normalize_bibtex_for_regex(&s);
-                combined.push_str(&normalized);
-                combined.push('\n');
-            }
+        let s = match std::fs::read_to_string(p) {
+            Ok(s) => s,
             Err(e) => {
                 eprintln!(
                     "{} {} ({})",
@@ -151,52 +159,44 @@ fn run_add_bib_files(paths: Vec<String>) {
                 );
                 return;
             }
+        };
+
+        match import_extension(p).as_deref() {
+            Some("json") => match parse_csl_json(&s) {
+                Ok(parsed) => refs.extend(parsed),
+                Err(e) => {
+                    eprintln!(
+                        "{} {} ({})",
+                        "❌ Invalid CSL-JSON in".red().bold(),
+                        p,
+                        e
+                    );
+                    return;
+                }
+            },
+            Some("ris") => refs.extend(parse_ris(&s)),
+            Some("nbib") => refs.extend(parse_nbib(&s)),
+            _ => {
+                let parsed = parse_bibtex(&s);
+                print_parse_errors(p, &parsed.errors);
+                refs.extend(parsed.refs);
+                preambles.extend(parsed.preambles);
+            }
         }
     }
 
-    let refs = parse_bibtex(&combined);
-
     if refs.is_empty() {
-        eprintln!("{}", "❌ No BibTeX entries detected".red().bold());
+        eprintln!("{}", "❌ No references detected".red().bold());
         return;
     }
 
-    let index = load_index();
-    let mut project = index
-        .active_project
-        .as_ref()
-        .map(|pid| load_project(pid));
-
-    for r in refs {
-        let rid = r.id.clone();
-        create_or_update_ref(r);
-
-        if let Some(ref mut p) = project {
-            if !p.refs.contains(&rid) {
-                p.refs.push(rid);
-            }
-        }
-    }
-
-    if let Some(p) = project {
-        save_project(&p);
-        println!(
-            "{}",
-            format!("🔗 Attached references to project '{}'", p.id)
-                .bright_green()
-                .bold()
-        );
-    }
+    store_and_pin(refs, preambles);
 }
 
 
 fn run_add_manual(args: Vec<String>) {
     let title = args[0].clone();
-    let authors: Vec<String> = args[1]
-        .split(" and ")
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect();
+    let authors = parse_names(&args[1]);
 
     if authors.is_empty() {
         eprintln!("{}", "❌ Author field cannot be empty".red().bold());
@@ -205,10 +205,8 @@ fn run_add_manual(args: Vec<String>) {
 
     let year = args.get(2).and_then(|y| y.parse::<u16>().ok());
 
-    let id = make_ref_id(&authors, year, &title);
-
-    let reference = Reference {
-        id: id.clone(),
+    let mut reference = Reference {
+        id: String::new(),
         sid: make_sid(), // ✅ REQUIRED
         kind: RefKind::Article,
         title,
@@ -216,22 +214,17 @@ fn run_add_manual(args: Vec<String>) {
         editors: vec![],
         year,
         identifiers: Identifiers::default(),
-        venue: Some(Venue {
-            journal: None,
-            booktitle: None,
-            publisher: None,
-            series: None,
-            volume: None,
-            issue: None,
-            pages: None,
-            address: None,
-        }),
+        venue: Some(Venue::default()),
         tags: vec![],
         notes: None,
-        attachments: Vec::new()
+        attachments: Vec::new(),
+        extra: BTreeMap::new(),
+        aliases: Vec::new(),
     };
 
-    create_or_update_ref(reference);
+    reference.id = make_key(&reference);
+
+    let id = create_or_update_ref(reference);
     attach_to_active_project(&id);
 
     println!(
@@ -254,70 +247,79 @@ fn run_add_bibtex() {
         return;
     }
 
-    let normalized = normalize_bibtex_for_regex(&input);
-    let refs = parse_bibtex(&normalized);
+    let parsed = parse_bibtex(&input);
+    print_parse_errors("<stdin>", &parsed.errors);
 
-    if refs.is_empty() {
-        eprintln!(
-            "{}\n{}\n{}",
-            "❌ No BibTeX entries detected".red().bold(),
-            "Elaine expects each BibTeX entry to end with a closing brace.".yellow(),
-            "↳ Make sure the last line is just:    }".yellow()
-        );
+    if parsed.refs.is_empty() {
+        eprintln!("{}", "❌ No BibTeX entries detected".red().bold());
         return;
     }
 
+    store_and_pin(parsed.refs, parsed.preambles);
+}
+
+
+// --------------------------------------------------
+// Helpers
+// --------------------------------------------------
+/// Lowercased extension of an importable file
+fn import_extension(path: &str) -> Option<String> {
+    std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+}
+
+fn is_import_file(arg: &str) -> bool {
+    matches!(import_extension(arg).as_deref(), Some("bib" | "json" | "ris" | "nbib"))
+        && std::path::Path::new(arg).exists()
+}
+
+
+/// Store imported references and pin them (plus any `@preamble`
+/// blocks) to the active library, if one is set.
+fn store_and_pin(refs: Vec<Reference>, preambles: Vec<String>) {
     let index = load_index();
-    let active_project = index.active_project.clone();
-    let mut project = active_project
+    let mut project = index
+        .active_project
         .as_ref()
         .map(|pid| load_project(pid));
 
     for r in refs {
-        let rid = r.id.clone();
-        create_or_update_ref(r);
+        let rid = create_or_update_ref(r);
 
-        if let Some(ref mut proj) = project {
-            if !proj.refs.contains(&rid) {
-                proj.refs.push(rid);
-            }
+        if let Some(ref mut p) = project
+            && !p.refs.contains(&rid) {
+            p.refs.push(rid);
         }
     }
 
-    if let Some(p) = project {
-        save_project(&p);
-        println!(
-            "{}",
-            format!("🔗 Attached references to project '{}'", p.id)
-                .bright_green()
-                .bold()
-        );
-    }
-}
-
-
-// --------------------------------------------------
-// Helpers
-// --------------------------------------------------
-fn is_bib_file(arg: &str) -> bool {
-    arg.ends_with(".bib") && std::path::Path::new(arg).exists()
-}
-
-fn normalize_bibtex_for_regex(input: &str) -> String {
-    input
-        .lines()
-        .map(|line| {
-            let trimmed = line.trim_start();
-            if trimmed == "}" {
-                "}".to_string()
-            } else {
-                line.to_string()
+    match project {
+        Some(mut p) => {
+            for pre in preambles {
+                if !p.preambles.contains(&pre) {
+                    p.preambles.push(pre);
+                }
             }
-        })
-        .collect::<Vec<_>>()
-        .join("\n")
-}
 
+            save_project(&p);
+            println!(
+                "{}",
+                format!("🔗 Attached references to project '{}'", p.id)
+                    .bright_green()
+                    .bold()
+            );
+        }
+        None if !preambles.is_empty() => {
+            println!(
+                "{}",
+                "⚠️  No active library: @preamble blocks were not stored."
+                    .yellow()
+            );
+        }
+        None => {}
+    }
+}
 
 fn attach_to_active_project(ref_id: &str) {
     let index = load_index();
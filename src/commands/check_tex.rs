@@ -0,0 +1,238 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use colored::*;
+use strsim::normalized_levenshtein;
+
+use crate::commands::purge::{collect_bib_keys, collect_tex_files, scan_citations};
+use crate::project_store::{load_project, save_project};
+use crate::reference_store::load_all_refs;
+use crate::state::{elaine_dir, load_index};
+use crate::utils::resolve_project::{print_project_resolve_error, resolve_project};
+
+/// Minimum key similarity (normalized Levenshtein) for a suggestion
+const KEY_SIMILARITY: f64 = 0.75;
+
+/// Suggestions shown per undefined key
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Entry point for `eln check-tex <path>`
+///
+/// Cross-references every cited key against the library, the whole
+/// registry and the `.bib` files under `path`. Keys found only in the
+/// registry are reported as unpinned (`--pin` pins them); keys found
+/// nowhere are undefined and exit with status 1.
+pub fn run_check_tex(path: String, library: Option<String>, pin: bool) {
+    let root = Path::new(&path);
+
+    if !root.exists() {
+        eprintln!("{}", "❌ Provided path does not exist".red());
+        return;
+    }
+
+    // --- Library ----------------------------------------------------------
+
+    let has_registry = elaine_dir().exists();
+
+    let pid = match library {
+        Some(sel) => match resolve_project(&sel) {
+            Ok(p) => Some(p),
+            Err(e) => {
+                print_project_resolve_error(e);
+                return;
+            }
+        },
+        None if has_registry => load_index().active_project,
+        None => None,
+    };
+
+    if pin && pid.is_none() {
+        eprintln!("{}", "❌ No active project set.".red());
+        return;
+    }
+
+    // --- Known keys -------------------------------------------------------
+
+    // Key (ID or alias) → reference ID
+    let mut registry: HashMap<String, String> = HashMap::new();
+    if has_registry {
+        for r in load_all_refs() {
+            for alias in &r.aliases {
+                registry.insert(alias.clone(), r.id.clone());
+            }
+            registry.insert(r.id.clone(), r.id);
+        }
+    }
+
+    let pinned: HashSet<String> = pid
+        .as_deref()
+        .map(|p| load_project(p).refs.into_iter().collect())
+        .unwrap_or_default();
+
+    let (bib_keys, bib_count) = collect_bib_keys(root);
+
+    // --- Cited keys with locations -----------------------------------------
+
+    let mut cited: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for file in collect_tex_files(root) {
+        let content = fs::read_to_string(&file).unwrap_or_default();
+        let shown = file.strip_prefix(root).unwrap_or(&file).display().to_string();
+
+        for c in scan_citations(&content) {
+            if c.key != "*" {
+                cited
+                    .entry(c.key)
+                    .or_default()
+                    .push(format!("{}:{}", shown, c.line));
+            }
+        }
+    }
+
+    println!(
+        "{}",
+        format!(
+            "🔍 Checking {} cited key(s) against {}the registry and {} .bib file(s)",
+            cited.len(),
+            pid.as_deref()
+                .map(|p| format!("library '{}', ", p))
+                .unwrap_or_default(),
+            bib_count
+        )
+        .bold()
+    );
+
+    // --- Classify ---------------------------------------------------------
+
+    let mut unpinned: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+    let mut undefined: Vec<&String> = Vec::new();
+
+    for key in cited.keys() {
+        match registry.get(key) {
+            Some(id) if pinned.contains(id) => {}
+            Some(id) => {
+                // The .bib covers it, but the library would not
+                if pid.is_some() {
+                    unpinned.entry(id.clone()).or_default().push(key);
+                }
+            }
+            None if bib_keys.contains(key) => {}
+            None => undefined.push(key),
+        }
+    }
+
+    // --- Report -----------------------------------------------------------
+
+    if !unpinned.is_empty() {
+        let pid = pid.as_deref().unwrap_or_default();
+
+        println!();
+        println!(
+            "{}",
+            format!("⚠️  In the registry but not pinned to '{}':", pid).yellow().bold()
+        );
+
+        for (id, keys) in &unpinned {
+            for key in keys {
+                let alias = if *key != id {
+                    format!(" (alias of '{}')", id)
+                } else {
+                    String::new()
+                };
+
+                println!(
+                    "  {}{}  {}",
+                    key.yellow(),
+                    alias.dimmed(),
+                    cited[*key].join(", ").dimmed()
+                );
+            }
+        }
+
+        if pin {
+            let mut project = load_project(pid);
+            for id in unpinned.keys() {
+                if !project.refs.contains(id) {
+                    project.refs.push(id.clone());
+                }
+            }
+            save_project(&project);
+
+            println!(
+                "{}",
+                format!("📌 Pinned {} reference(s) → '{}'", unpinned.len(), pid)
+                    .bright_green()
+                    .bold()
+            );
+        } else {
+            println!("  {}", "→ run again with --pin to pin them".cyan());
+        }
+    }
+
+    if !undefined.is_empty() {
+        let mut candidates: Vec<&String> = registry.keys().chain(bib_keys.iter()).collect();
+        candidates.sort();
+        candidates.dedup();
+
+        println!();
+        println!("{}", "❌ Undefined citations:".red().bold());
+
+        for key in &undefined {
+            println!("  {}  {}", key.red(), cited[*key].join(", ").dimmed());
+
+            let suggestions = suggest(key, &candidates);
+            if !suggestions.is_empty() {
+                println!("      {} {}", "did you mean:".cyan(), suggestions.join(", "));
+            }
+        }
+    }
+
+    println!();
+
+    if undefined.is_empty() {
+        println!(
+            "{}",
+            "✔️  Every citation is defined".bright_green().bold()
+        );
+    } else {
+        println!(
+            "{}",
+            format!("❌ {} undefined citation key(s)", undefined.len())
+                .red()
+                .bold()
+        );
+        std::process::exit(1);
+    }
+}
+
+
+/// Close matches for an undefined key: prefix matches either way (as
+/// selectors resolve), then keys within typo distance, best first.
+fn suggest(key: &str, candidates: &[&String]) -> Vec<String> {
+    let lower = key.to_lowercase();
+
+    let mut scored: Vec<(f64, &String)> = candidates
+        .iter()
+        .filter_map(|c| {
+            let other = c.to_lowercase();
+            let similarity = normalized_levenshtein(&lower, &other);
+
+            if other.starts_with(&lower) || lower.starts_with(&other) {
+                Some((similarity + 1.0, *c))
+            } else if similarity >= KEY_SIMILARITY {
+                Some((similarity, *c))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, c)| c.clone())
+        .collect()
+}
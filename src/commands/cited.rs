@@ -0,0 +1,44 @@
+use std::path::Path;
+
+use colored::*;
+
+use crate::commands::purge::read_cited_keys;
+
+/// Entry point for `eln cited <path>`
+///
+/// Lists the keys a LaTeX project cites, read from the last compile's
+/// `.aux` / `.bcf` files when present, otherwise from the `.tex` sources.
+pub fn run_cited(path: String, source: String) {
+    let root = Path::new(&path);
+
+    if !root.exists() {
+        eprintln!("{}", "❌ Provided path does not exist".red());
+        return;
+    }
+
+    let Some(cited) = read_cited_keys(root, &source) else {
+        return;
+    };
+
+    for file in &cited.files {
+        println!("  {}", file.display().to_string().dimmed());
+    }
+
+    let mut keys: Vec<&String> = cited.keys.iter().filter(|k| *k != "*").collect();
+    keys.sort();
+
+    println!();
+    for key in &keys {
+        println!("{}", key);
+    }
+
+    println!();
+    println!(
+        "{}",
+        format!("✔️  {} cited key(s)", keys.len()).bright_green().bold()
+    );
+
+    if cited.keys.contains("*") {
+        println!("{}", "⚠️  \\nocite{*}: the whole bibliography is cited".yellow());
+    }
+}
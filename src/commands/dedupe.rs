@@ -0,0 +1,309 @@
+use std::collections::{BTreeMap, HashMap};
+
+use colored::*;
+use strsim::normalized_levenshtein;
+
+use crate::bibtex::latex::ascii_fold;
+use crate::reference::{RefKind, Reference};
+use crate::reference_store::load_all_refs;
+use crate::state::elaine_dir;
+use crate::utils::normalize::strip_doi_prefix;
+
+/// Minimum title similarity (normalized Levenshtein) for a fuzzy match
+const TITLE_SIMILARITY: f64 = 0.9;
+
+/// Values wider than this are cut in the side-by-side view
+const COLUMN_WIDTH: usize = 38;
+
+/// Entry point for `eln dedupe`
+///
+/// Lists pairs of references that look like the same work, with a
+/// field-by-field comparison and the `eln merge` command to combine them.
+/// Nothing is changed.
+pub fn run_dedupe() {
+    if !elaine_dir().exists() {
+        eprintln!(
+            "{}",
+            "❌ .elaine/ directory not found. Run `eln init` first."
+                .red()
+                .bold()
+        );
+        return;
+    }
+
+    let mut refs = load_all_refs();
+    refs.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let candidates = find_duplicates(&refs);
+
+    if candidates.is_empty() {
+        println!("{}", "✔️  No duplicate candidates found".bright_green().bold());
+        return;
+    }
+
+    println!(
+        "{}",
+        format!("🔍 {} duplicate candidate(s)", candidates.len())
+            .yellow()
+            .bold()
+    );
+
+    for (n, ((i, j), reason)) in candidates.iter().enumerate() {
+        let (keep, drop) = suggest_keep(&refs[*i], &refs[*j]);
+
+        println!();
+        println!(
+            "[{}] {} ↔ {}  {}",
+            n + 1,
+            keep.id.bold(),
+            drop.id.bold(),
+            format!("({})", reason).dimmed()
+        );
+        print_side_by_side(keep, drop);
+        println!("    {}", format!("→ eln merge {} {}", keep.id, drop.id).cyan());
+    }
+}
+
+
+/// Candidate pairs (indices into `refs`, lower first) with the reason
+/// they matched. Identifier matches win over fuzzy ones.
+fn find_duplicates(refs: &[Reference]) -> BTreeMap<(usize, usize), String> {
+    let mut pairs: BTreeMap<(usize, usize), String> = BTreeMap::new();
+
+    // --- Identifiers ----------------------------------------------------------
+
+    let mut by_identifier: BTreeMap<(&str, String), Vec<usize>> = BTreeMap::new();
+
+    for (i, r) in refs.iter().enumerate() {
+        let ids = &r.identifiers;
+
+        if let Some(doi) = ids.doi.as_deref().map(|d| strip_doi_prefix(d).to_lowercase()) {
+            by_identifier.entry(("DOI", doi)).or_default().push(i);
+        }
+        if let Some(arxiv) = ids.arxiv.as_deref().map(normalize_arxiv) {
+            by_identifier.entry(("arXiv ID", arxiv)).or_default().push(i);
+        }
+        // Chapters share their book's ISBN
+        if !is_part_of_book(r.kind)
+            && let Some(isbn) = ids.isbn.as_deref().and_then(normalize_isbn)
+        {
+            by_identifier.entry(("ISBN", isbn)).or_default().push(i);
+        }
+    }
+
+    for ((label, _), members) in &by_identifier {
+        for (n, &i) in members.iter().enumerate() {
+            for &j in &members[n + 1..] {
+                pairs.entry((i, j)).or_insert_with(|| format!("same {}", label));
+            }
+        }
+    }
+
+    // --- Fuzzy title + year + first author -------------------------------------
+
+    let mut by_author: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, r) in refs.iter().enumerate() {
+        if let Some(key) = first_author_key(r) {
+            by_author.entry(key).or_default().push(i);
+        }
+    }
+
+    let titles: Vec<String> = refs.iter().map(|r| normalize_title(&r.title)).collect();
+
+    for members in by_author.values() {
+        for (n, &i) in members.iter().enumerate() {
+            for &j in &members[n + 1..] {
+                if pairs.contains_key(&(i, j)) || !years_close(&refs[i], &refs[j]) {
+                    continue;
+                }
+
+                let similarity = normalized_levenshtein(&titles[i], &titles[j]);
+                if similarity >= TITLE_SIMILARITY {
+                    pairs.insert(
+                        (i, j),
+                        format!("similar title, author and year ({:.0}%)", similarity * 100.0),
+                    );
+                }
+            }
+        }
+    }
+
+    pairs
+}
+
+
+/// Prefer the published version (has a DOI), then the one with more
+/// attachments; otherwise keep the first by ID.
+fn suggest_keep<'a>(a: &'a Reference, b: &'a Reference) -> (&'a Reference, &'a Reference) {
+    let score = |r: &Reference| (r.identifiers.doi.is_some(), r.attachments.len());
+
+    if score(b) > score(a) { (b, a) } else { (a, b) }
+}
+
+
+/// Field-by-field comparison; differing rows are highlighted.
+pub fn print_side_by_side(a: &Reference, b: &Reference) {
+    println!(
+        "    {:<10} {} {}",
+        "",
+        pad(&a.id, COLUMN_WIDTH).bold(),
+        b.id.bold()
+    );
+
+    for ((name, left), (_, right)) in summary_fields(a).into_iter().zip(summary_fields(b)) {
+        if left.is_empty() && right.is_empty() {
+            continue;
+        }
+
+        let line = format!(
+            "    {:<10} {} {}",
+            name,
+            pad(&left, COLUMN_WIDTH),
+            cut(&right, COLUMN_WIDTH)
+        );
+
+        if left == right {
+            println!("{}", line.dimmed());
+        } else {
+            println!("{}", line.yellow());
+        }
+    }
+}
+
+
+fn summary_fields(r: &Reference) -> Vec<(&'static str, String)> {
+    let venue = r.venue.as_ref();
+    let venue_field = |f: fn(&crate::reference::Venue) -> Option<&String>| {
+        venue.and_then(f).cloned().unwrap_or_default()
+    };
+    let opt = |v: &Option<String>| v.clone().unwrap_or_default();
+
+    vec![
+        ("kind", r.kind.bibtex_name().to_string()),
+        ("title", r.title.clone()),
+        (
+            "authors",
+            r.authors.iter().map(|n| n.display()).collect::<Vec<_>>().join("; "),
+        ),
+        ("year", r.year.map(|y| y.to_string()).unwrap_or_default()),
+        ("journal", venue_field(|v| v.journal.as_ref())),
+        ("booktitle", venue_field(|v| v.booktitle.as_ref())),
+        ("volume", venue_field(|v| v.volume.as_ref())),
+        ("pages", venue_field(|v| v.pages.as_ref())),
+        ("publisher", venue_field(|v| v.publisher.as_ref())),
+        ("doi", opt(&r.identifiers.doi)),
+        ("arxiv", opt(&r.identifiers.arxiv)),
+        ("isbn", opt(&r.identifiers.isbn)),
+        ("pmid", opt(&r.identifiers.pmid)),
+        ("url", opt(&r.identifiers.url)),
+        ("tags", r.tags.join(", ")),
+        (
+            "files",
+            if r.attachments.is_empty() {
+                String::new()
+            } else {
+                format!("{} attachment(s)", r.attachments.len())
+            },
+        ),
+        ("notes", r.notes.clone().unwrap_or_default()),
+    ]
+}
+
+
+// --------------------------------------------------
+// Normalization
+// --------------------------------------------------
+
+/// `arXiv:2101.00001v2` → `2101.00001`
+fn normalize_arxiv(id: &str) -> String {
+    let id = id.trim().to_lowercase();
+    let id = id.strip_prefix("arxiv:").unwrap_or(&id);
+
+    match id.rsplit_once('v') {
+        Some((base, version))
+            if !base.is_empty()
+                && !version.is_empty()
+                && version.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            base.to_string()
+        }
+        _ => id.to_string(),
+    }
+}
+
+/// Digits only, with ISBN-10 converted to ISBN-13
+fn normalize_isbn(isbn: &str) -> Option<String> {
+    let digits: String = isbn
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == 'x' || *c == 'X')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    match digits.len() {
+        13 => Some(digits),
+        10 => {
+            let core = format!("978{}", &digits[..9]);
+            let sum: u32 = core
+                .chars()
+                .enumerate()
+                .map(|(i, c)| c.to_digit(10).unwrap_or(0) * if i % 2 == 0 { 1 } else { 3 })
+                .sum();
+            Some(format!("{}{}", core, (10 - sum % 10) % 10))
+        }
+        _ => None,
+    }
+}
+
+fn normalize_title(title: &str) -> String {
+    ascii_fold(title)
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn first_author_key(r: &Reference) -> Option<String> {
+    let family: String = ascii_fold(&r.authors.first()?.family_plain())
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect();
+
+    (!family.is_empty()).then_some(family)
+}
+
+/// Preprints and their published versions are often a year apart
+fn years_close(a: &Reference, b: &Reference) -> bool {
+    match (a.year, b.year) {
+        (Some(x), Some(y)) => x.abs_diff(y) <= 1,
+        _ => true,
+    }
+}
+
+fn is_part_of_book(kind: RefKind) -> bool {
+    matches!(
+        kind,
+        RefKind::InCollection | RefKind::InBook | RefKind::InProceedings
+    )
+}
+
+fn cut(s: &str, width: usize) -> String {
+    let s = s.lines().next().unwrap_or_default();
+
+    if s.chars().count() <= width {
+        s.to_string()
+    } else {
+        let mut out: String = s.chars().take(width - 1).collect();
+        out.push('…');
+        out
+    }
+}
+
+fn pad(s: &str, width: usize) -> String {
+    let s = cut(s, width);
+    let fill = width.saturating_sub(s.chars().count());
+    format!("{}{}", s, " ".repeat(fill))
+}
@@ -3,10 +3,13 @@ use std::fs;
 use std::path::Path;
 use std::io::{stdin, stdout, Write};
 
-use crate::reference_store::{load_ref, save_ref};
-use crate::utils::id::make_ref_id;
+use crate::bibtex::is_mapped_field;
+use crate::bibtex::names::{join_names, parse_names, Name};
+use crate::reference_store::{available_ref_id, load_ref, save_ref};
+use crate::utils::citekey::make_key;
 use crate::utils::resolve::{resolve_reference, print_resolve_error};
 use crate::project::Project;
+use crate::reference::{RefKind, Reference};
 use crate::state::elaine_dir;
 
 pub fn run_edit(selector: String) {
@@ -40,8 +43,8 @@ pub fn run_edit(selector: String) {
     // ---- Core fields ---------------------------------------------------
 
     let title = prompt_edit("Title", &reference.title);
-    let authors = prompt_list("Authors", &reference.authors);
-    let editors = prompt_list("Editors", &reference.editors);
+    let authors = prompt_names("Authors", &reference.authors);
+    let editors = prompt_names("Editors", &reference.editors);
     let year = prompt_optional_u16("Year", reference.year);
 
     // ---- Kind ----------------------------------------------------------
@@ -57,9 +60,20 @@ pub fn run_edit(selector: String) {
         v.publisher = prompt_opt("Publisher", v.publisher.as_deref());
         v.series = prompt_opt("Series", v.series.as_deref());
         v.volume = prompt_opt("Volume", v.volume.as_deref());
-        v.issue = prompt_opt("Issue", v.issue.as_deref());
+        v.issue = prompt_opt("Issue / Number", v.issue.as_deref());
         v.pages = prompt_opt("Pages", v.pages.as_deref());
         v.address = prompt_opt("Address", v.address.as_deref());
+
+        // Type-specific fields: shown for matching kinds, or when already set
+        if kind.is_thesis() || v.school.is_some() {
+            v.school = prompt_opt("School", v.school.as_deref());
+        }
+        if kind == RefKind::TechReport || v.institution.is_some() {
+            v.institution = prompt_opt("Institution", v.institution.as_deref());
+        }
+        if kind.is_thesis() || kind == RefKind::TechReport || v.entry_type.is_some() {
+            v.entry_type = prompt_opt("Type", v.entry_type.as_deref());
+        }
     }
 
     // ---- Identifiers ---------------------------------------------------
@@ -69,6 +83,17 @@ pub fn run_edit(selector: String) {
     reference.identifiers.url = prompt_opt("URL", reference.identifiers.url.as_deref());
     reference.identifiers.isbn = prompt_opt("ISBN", reference.identifiers.isbn.as_deref());
     reference.identifiers.arxiv = prompt_opt("arXiv", reference.identifiers.arxiv.as_deref());
+    if reference.identifiers.arxiv.is_some() {
+        reference.identifiers.arxiv_class =
+            prompt_opt("arXiv class", reference.identifiers.arxiv_class.as_deref());
+    }
+    reference.identifiers.pmid = prompt_opt("PMID", reference.identifiers.pmid.as_deref());
+    reference.identifiers.pmcid = prompt_opt("PMCID", reference.identifiers.pmcid.as_deref());
+
+    // ---- Extra fields --------------------------------------------------
+
+    println!("{}", "--- Extra fields ('-' removes) ---".dimmed());
+    edit_extra(&mut reference.extra);
 
     reference.title = title;
     reference.authors = authors;
@@ -78,7 +103,8 @@ pub fn run_edit(selector: String) {
 
     // ---- ID reconciliation --------------------------------------------
 
-    let new_id = make_ref_id(&reference.authors, reference.year, &reference.title);
+    // A key held by another atom gets a suffix rather than clobbering it
+    let new_id = available_ref_id(&make_key(&reference), &ref_id);
 
     if new_id != ref_id {
         println!(
@@ -96,8 +122,7 @@ pub fn run_edit(selector: String) {
             return;
         }
 
-        rename_reference(&ref_id, &new_id);
-        reference.id = new_id.clone();
+        rename_reference(&mut reference, &new_id);
     }
 
     save_ref(&reference);
@@ -126,14 +151,11 @@ fn prompt_edit(label: &str, current: &str) -> String {
     }
 }
 
-fn prompt_list(label: &str, current: &[String]) -> Vec<String> {
-    let joined = current.join(" and ");
+fn prompt_names(label: &str, current: &[Name]) -> Vec<Name> {
+    let joined = join_names(current);
     let raw = prompt_edit(label, &joined);
 
-    raw.split(" and ")
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect()
+    parse_names(&raw)
 }
 
 fn prompt_opt(label: &str, current: Option<&str>) -> Option<String> {
@@ -168,25 +190,65 @@ fn prompt_optional_u16(label: &str, current: Option<u16>) -> Option<u16> {
     }
 }
 
-fn prompt_kind(current: crate::reference::RefKind) -> crate::reference::RefKind {
-    use crate::reference::RefKind::*;
-
+fn prompt_kind(current: RefKind) -> RefKind {
     print!("Kind [{:?}]: ", current);
     stdout().flush().unwrap();
 
     let mut input = String::new();
     stdin().read_line(&mut input).unwrap();
-    let v = input.trim().to_lowercase();
 
-    match v.as_str() {
-        "" => current,
-        "article" => Article,
-        "inproceedings" => InProceedings,
-        "incollection" => InCollection,
-        "inbook" => InBook,
-        "book" => Book,
-        "misc" => Misc,
-        _ => current,
+    RefKind::from_bibtex(input.trim()).unwrap_or(current)
+}
+
+/// Edit unrecognised BibTeX fields in place, then offer to add new ones.
+fn edit_extra(extra: &mut std::collections::BTreeMap<String, String>) {
+    let keys: Vec<String> = extra.keys().cloned().collect();
+
+    for key in keys {
+        let current = extra[&key].clone();
+        let v = prompt_edit(&key, &current);
+
+        if v == "-" {
+            extra.remove(&key);
+        } else {
+            extra.insert(key, v);
+        }
+    }
+
+    loop {
+        print!("Add field (name = value) [optional]: ");
+        stdout().flush().unwrap();
+
+        let mut input = String::new();
+        stdin().read_line(&mut input).unwrap();
+        let v = input.trim();
+
+        if v.is_empty() {
+            return;
+        }
+
+        let Some((name, value)) = v.split_once('=') else {
+            println!("{}", "⚠️  Expected: name = value".yellow());
+            continue;
+        };
+
+        let name = name.trim().to_lowercase();
+        let value = value.trim().to_string();
+
+        if name.is_empty() || value.is_empty() {
+            println!("{}", "⚠️  Expected: name = value".yellow());
+            continue;
+        }
+
+        if is_mapped_field(&name) {
+            println!(
+                "{}",
+                format!("⚠️  '{}' has its own prompt above", name).yellow()
+            );
+            continue;
+        }
+
+        extra.insert(name, value);
     }
 }
 
@@ -203,9 +265,19 @@ fn confirm(msg: &str) -> bool {
 
 
 
-pub fn rename_reference(old_id: &str, new_id: &str) {
-    rename_reference_file(old_id, new_id);
-    update_all_libraries(old_id, new_id);
+/// Move `reference` to `new_id`: renames the atom, repoints every
+/// library and keeps the old ID as an alias so existing `\cite` keys
+/// still resolve. The caller saves the updated reference.
+pub fn rename_reference(reference: &mut Reference, new_id: &str) {
+    let old_id = std::mem::replace(&mut reference.id, new_id.to_string());
+
+    rename_reference_file(&old_id, new_id);
+    update_all_libraries(&old_id, new_id);
+
+    reference.aliases.retain(|a| a != new_id);
+    if !reference.aliases.contains(&old_id) {
+        reference.aliases.push(old_id);
+    }
 }
 
 fn rename_reference_file(old_id: &str, new_id: &str) {
@@ -216,7 +288,8 @@ fn rename_reference_file(old_id: &str, new_id: &str) {
         .expect("❌ Failed to rename reference file");
 }
 
-fn update_all_libraries(old_id: &str, new_id: &str) {
+/// Point every library at `new_id` instead of `old_id`.
+pub fn update_all_libraries(old_id: &str, new_id: &str) {
     let projects_dir = elaine_dir().join("projects");
 
     if let Ok(entries) = fs::read_dir(&projects_dir) {
@@ -249,6 +322,12 @@ fn update_library_refs(path: &Path, old_id: &str, new_id: &str) {
         }
     }
 
+    // After a merge the library may already hold `new_id`
+    if changed {
+        let mut seen = std::collections::HashSet::new();
+        project.refs.retain(|rid| seen.insert(rid.clone()));
+    }
+
     if changed {
         let new_contents =
             serde_yaml::to_string(&project)
@@ -0,0 +1,62 @@
+use std::fs;
+
+use colored::*;
+
+use crate::commands::printed::{select_references, sort_references};
+use crate::csl::render_csl_json;
+use crate::ris::render_ris;
+
+/// Entry point for `eln export`
+///
+/// Library selection mirrors `eln printed`: `--all`, explicit libraries,
+/// or the active library.
+pub fn run_export(format: String, all: bool, projects: Vec<String>) {
+    let mut selection = match select_references(all, projects) {
+        Some(s) => s,
+        None => return,
+    };
+
+    // --- Deterministic ordering ---------------------------------------------
+    sort_references(&mut selection.refs);
+
+    let (out, extension) = match format.as_str() {
+        "csl-json" => (render_csl_json(&selection.refs), "json"),
+        "ris" => (render_ris(&selection.refs), "ris"),
+        other => {
+            eprintln!(
+                "{}",
+                format!("❌ Unknown export format '{}'", other)
+                    .red()
+                    .bold()
+            );
+            return;
+        }
+    };
+
+    // --- Determine output filename ----------------------------------------
+
+    let out_name = if all {
+        format!("global_references.{}", extension)
+    } else {
+        format!(
+            "{}_references.{}",
+            selection.project_ids.join("+"),
+            extension
+        )
+    };
+
+    fs::write(&out_name, out + "\n")
+        .expect("❌ Failed writing export file");
+
+    println!(
+        "{}",
+        format!(
+            "📤 Exported {} references ({}) to → {}",
+            selection.refs.len(),
+            format,
+            out_name
+        )
+        .green()
+        .bold()
+    );
+}
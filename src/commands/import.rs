@@ -0,0 +1,197 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use colored::*;
+
+use crate::migrate::endnote::parse_endnote_xml;
+use crate::migrate::zotero::parse_zotero_rdf;
+use crate::migrate::{Migration, Skipped};
+use crate::project_store::{create_project_if_missing, load_project, save_project};
+use crate::reference_store::create_or_update_ref;
+use crate::state::{elaine_dir, load_index};
+
+/// Skipped items listed per reason before the rest are summarised
+const SKIPPED_PREVIEW: usize = 5;
+
+/// Entry point for `eln import`
+///
+/// Collections become libraries; items outside any collection are pinned
+/// to the active library, like `eln add`.
+pub fn run_import(from: String, path: String) {
+    if !elaine_dir().exists() {
+        eprintln!(
+            "{}",
+            "❌ .elaine/ directory not found. Run `eln init` first."
+                .red()
+                .bold()
+        );
+        return;
+    }
+
+    let input = match fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{} {} ({})", "❌ Failed to read".red().bold(), path, e);
+            return;
+        }
+    };
+
+    // Attachment paths in exports are relative to the export file
+    let base = Path::new(&path)
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+
+    let parsed = match from.as_str() {
+        "endnote-xml" => parse_endnote_xml(&input, base),
+        "zotero-rdf" => parse_zotero_rdf(&input, base),
+        other => Err(format!("unknown source format '{}'", other)),
+    };
+
+    let migration = match parsed {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!(
+                "{}\n{}",
+                format!("❌ Failed to parse {}", path).red().bold(),
+                e.dimmed()
+            );
+            return;
+        }
+    };
+
+    store_migration(migration);
+}
+
+
+fn store_migration(migration: Migration) {
+    let Migration {
+        refs,
+        collections,
+        skipped,
+    } = migration;
+
+    let imported = refs.len();
+    let attachments: usize = refs.iter().map(|r| r.attachments.len()).sum();
+
+    let collected: HashSet<&String> = collections.values().flatten().collect();
+    let uncollected: Vec<String> = refs
+        .iter()
+        .map(|r| r.id.clone())
+        .filter(|id| !collected.contains(id))
+        .collect();
+
+    // IDs taken by different works in the registry are suffixed on store
+    let mut stored: HashMap<String, String> = HashMap::new();
+    for r in refs {
+        let id = r.id.clone();
+        stored.insert(id, create_or_update_ref(r));
+    }
+
+    let final_ids = |ids: &[String]| -> Vec<String> {
+        ids.iter().map(|id| stored.get(id).unwrap_or(id).clone()).collect()
+    };
+
+    // --- Collections → libraries --------------------------------------------
+
+    for (name, ids) in &collections {
+        let mut project = create_project_if_missing(&library_id(name));
+        pin_all(&mut project.refs, &final_ids(ids));
+        save_project(&project);
+    }
+
+    // --- Everything else → active library -----------------------------------
+
+    if !uncollected.is_empty() {
+        match load_index().active_project {
+            Some(pid) => {
+                let mut project = load_project(&pid);
+                pin_all(&mut project.refs, &final_ids(&uncollected));
+                save_project(&project);
+            }
+            None => println!(
+                "{}",
+                format!(
+                    "⚠️  No active library: {} references outside any collection were not pinned.",
+                    uncollected.len()
+                )
+                .yellow()
+            ),
+        }
+    }
+
+    // --- Summary ------------------------------------------------------------
+
+    println!(
+        "{}",
+        format!(
+            "📥 Imported {} references ({} attachments) into {} libraries",
+            imported,
+            attachments,
+            collections.len()
+        )
+        .bright_green()
+        .bold()
+    );
+
+    print_skipped(&skipped);
+}
+
+
+fn pin_all(project_refs: &mut Vec<String>, ids: &[String]) {
+    for id in ids {
+        if !project_refs.contains(id) {
+            project_refs.push(id.clone());
+        }
+    }
+}
+
+
+/// Collection names may contain spaces and slashes; library IDs are
+/// file stems.
+fn library_id(name: &str) -> String {
+    name.trim()
+        .split(|c: char| c.is_whitespace() || c == '/' || c == '\\')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+
+fn print_skipped(skipped: &[Skipped]) {
+    if skipped.is_empty() {
+        return;
+    }
+
+    // `attachment not found: <file>` groups under its prefix, with the
+    // detail kept next to the item
+    let mut by_reason: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for s in skipped {
+        let (reason, item) = match s.reason.split_once(": ") {
+            Some((reason, detail)) => (reason, format!("{} — {}", s.item, detail)),
+            None => (s.reason.as_str(), s.item.clone()),
+        };
+        by_reason.entry(reason).or_default().push(item);
+    }
+
+    println!(
+        "{}",
+        format!("⚠️  Skipped {} item(s):", skipped.len()).yellow()
+    );
+
+    for (reason, items) in by_reason {
+        println!("  {} ({})", reason, items.len());
+
+        for item in items.iter().take(SKIPPED_PREVIEW) {
+            println!("    {}", item.dimmed());
+        }
+
+        if items.len() > SKIPPED_PREVIEW {
+            println!(
+                "    {}",
+                format!("… and {} more", items.len() - SKIPPED_PREVIEW).dimmed()
+            );
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use colored::*;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::commands::printed::{select_references, sort_references};
+use crate::reference::{RefKind, Reference};
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Serialize)]
+struct Issue {
+    id: String,
+    severity: Severity,
+    field: String,
+    message: String,
+}
+
+/// Machine-readable report emitted by `eln lint --format json`
+#[derive(Debug, Serialize)]
+struct Report {
+    libraries: Vec<String>,
+    checked: usize,
+    errors: usize,
+    warnings: usize,
+    issues: Vec<Issue>,
+}
+
+/// Entry point for `eln lint`
+///
+/// Exits with status 1 when any error is found, so it can gate CI.
+pub fn run_lint(all: bool, libs: Vec<String>, format: String) {
+    let mut selection = match select_references(all, libs) {
+        Some(s) => s,
+        None => std::process::exit(2),
+    };
+
+    sort_references(&mut selection.refs);
+
+    let mut issues = Vec::new();
+    for r in &selection.refs {
+        lint_reference(r, &mut issues);
+    }
+
+    let errors = issues
+        .iter()
+        .filter(|i| i.severity == Severity::Error)
+        .count();
+
+    let report = Report {
+        libraries: selection.project_ids,
+        checked: selection.refs.len(),
+        errors,
+        warnings: issues.len() - errors,
+        issues,
+    };
+
+    match format.as_str() {
+        "json" => println!(
+            "{}",
+            serde_json::to_string_pretty(&report)
+                .expect("❌ Failed to serialize lint report")
+        ),
+        _ => print_report(&report),
+    }
+
+    if report.errors > 0 {
+        std::process::exit(1);
+    }
+}
+
+// ============================================================
+// RULES
+// ============================================================
+
+/// Required fields per entry type. Each inner slice is a set of
+/// alternatives (e.g. `author` *or* `editor`).
+fn required_fields(kind: RefKind) -> &'static [&'static [&'static str]] {
+    use RefKind::*;
+
+    match kind {
+        Article => &[&["author"], &["title"], &["journal"], &["year"]],
+        Book => &[&["author", "editor"], &["title"], &["publisher"], &["year"]],
+        Booklet => &[&["title"]],
+        InBook => &[
+            &["author", "editor"],
+            &["title"],
+            &["chapter", "pages"],
+            &["publisher"],
+            &["year"],
+        ],
+        InCollection => &[
+            &["author"],
+            &["title"],
+            &["booktitle"],
+            &["publisher"],
+            &["year"],
+        ],
+        InProceedings => &[&["author"], &["title"], &["booktitle"], &["year"]],
+        Manual => &[&["title"]],
+        PhdThesis | MastersThesis => {
+            &[&["author"], &["title"], &["school"], &["year"]]
+        }
+        Proceedings => &[&["title"], &["year"]],
+        TechReport => &[&["author"], &["title"], &["institution"], &["year"]],
+        Unpublished => &[&["author"], &["title"], &["note"]],
+        Online => &[&["author", "editor"], &["title"], &["year"], &["url"]],
+        Dataset | Software => &[&["author", "editor"], &["title"], &["year"]],
+        Patent => &[&["author"], &["title"], &["number"], &["year"]],
+        Misc => &[],
+    }
+}
+
+/// Fields that BibTeX styles ignore for a given type; set values are
+/// usually a sign of a wrong `kind`.
+fn misplaced_fields(kind: RefKind) -> &'static [&'static str] {
+    use RefKind::*;
+
+    match kind {
+        Article => &["booktitle", "school", "institution"],
+        InProceedings | InCollection => &["journal", "school"],
+        Book => &["journal", "booktitle"],
+        PhdThesis | MastersThesis => &["journal", "booktitle"],
+        TechReport => &["journal", "booktitle", "school"],
+        _ => &[],
+    }
+}
+
+fn field_value<'a>(r: &'a Reference, name: &str) -> Option<&'a str> {
+    let v = r.venue.as_ref();
+
+    let value = match name {
+        "title" => Some(r.title.as_str()),
+        "author" => (!r.authors.is_empty()).then_some("…"),
+        "editor" => (!r.editors.is_empty()).then_some("…"),
+        "year" => r.year.map(|_| "…"),
+        "doi" => r.identifiers.doi.as_deref(),
+        "isbn" => r.identifiers.isbn.as_deref(),
+        "url" => r.identifiers.url.as_deref(),
+        "pmid" => r.identifiers.pmid.as_deref(),
+        "pmcid" => r.identifiers.pmcid.as_deref(),
+        "journal" => v.and_then(|v| v.journal.as_deref()),
+        "booktitle" => v.and_then(|v| v.booktitle.as_deref()),
+        "publisher" => v.and_then(|v| v.publisher.as_deref()),
+        "pages" => v.and_then(|v| v.pages.as_deref()),
+        "number" => v.and_then(|v| v.issue.as_deref()),
+        "school" => v.and_then(|v| v.school.as_deref()),
+        "institution" => v.and_then(|v| v.institution.as_deref()),
+        other => r.extra.get(other).map(String::as_str),
+    };
+
+    value.filter(|s| !s.trim().is_empty())
+}
+
+fn lint_reference(r: &Reference, issues: &mut Vec<Issue>) {
+    let mut push = |severity: Severity, field: &str, message: String| {
+        issues.push(Issue {
+            id: r.id.clone(),
+            severity,
+            field: field.to_string(),
+            message,
+        });
+    };
+
+    // --- Required / misplaced fields --------------------------------------
+
+    for alternatives in required_fields(r.kind) {
+        if !alternatives.iter().any(|f| field_value(r, f).is_some()) {
+            push(
+                Severity::Error,
+                &alternatives.join("|"),
+                format!(
+                    "missing required field for @{}",
+                    r.kind.bibtex_name()
+                ),
+            );
+        }
+    }
+
+    for f in misplaced_fields(r.kind) {
+        if field_value(r, f).is_some() {
+            push(
+                Severity::Warning,
+                f,
+                format!("ignored by @{} entries", r.kind.bibtex_name()),
+            );
+        }
+    }
+
+    // --- Value formats ----------------------------------------------------
+
+    if let Some(doi) = r.identifiers.doi.as_deref()
+        && !is_valid_doi(doi)
+    {
+        push(Severity::Error, "doi", format!("malformed DOI '{}'", doi));
+    }
+
+    if let Some(isbn) = r.identifiers.isbn.as_deref()
+        && !is_valid_isbn(isbn)
+    {
+        push(Severity::Error, "isbn", format!("invalid ISBN '{}'", isbn));
+    }
+
+    if let Some(year) = r.year
+        && !(1000..=current_year() + 1).contains(&year)
+    {
+        push(Severity::Error, "year", format!("implausible year {}", year));
+    }
+
+    if let Some(raw) = r.extra.get("year") {
+        push(Severity::Error, "year", format!("malformed year '{}'", raw));
+    }
+
+    if let Some(pages) = field_value(r, "pages")
+        && let Some(problem) = check_pages(pages)
+    {
+        push(Severity::Warning, "pages", problem);
+    }
+}
+
+// ============================================================
+// FORMAT CHECKS
+// ============================================================
+
+fn is_valid_doi(doi: &str) -> bool {
+    Regex::new(r"^10\.\d{4,9}/\S+$").unwrap().is_match(doi.trim())
+}
+
+/// ISBN-10 or ISBN-13 with a correct check digit.
+fn is_valid_isbn(isbn: &str) -> bool {
+    let chars: Vec<char> = isbn
+        .chars()
+        .filter(|c| !matches!(c, '-' | ' '))
+        .collect();
+
+    match chars.len() {
+        10 => {
+            let mut sum = 0;
+            for (i, c) in chars.iter().enumerate() {
+                let d = match c {
+                    'X' | 'x' if i == 9 => 10,
+                    c => match c.to_digit(10) {
+                        Some(d) => d,
+                        None => return false,
+                    },
+                };
+                sum += d * (10 - i as u32);
+            }
+            sum % 11 == 0
+        }
+        13 => {
+            let mut sum = 0;
+            for (i, c) in chars.iter().enumerate() {
+                let Some(d) = c.to_digit(10) else {
+                    return false;
+                };
+                sum += if i % 2 == 0 { d } else { d * 3 };
+            }
+            sum % 10 == 0
+        }
+        _ => false,
+    }
+}
+
+/// Returns a description of the problem, if any.
+fn check_pages(pages: &str) -> Option<String> {
+    let range = Regex::new(r"^([A-Za-z]*)(\d+)\s*(-+|–)\s*([A-Za-z]*)(\d+)$").unwrap();
+    let single = Regex::new(r"^[A-Za-z]*\d+$|^[ivxlcdm]+$").unwrap();
+    let pages = pages.trim();
+
+    if single.is_match(pages) || pages.contains(',') {
+        return None;
+    }
+
+    let Some(cap) = range.captures(pages) else {
+        return Some(format!("unrecognised page range '{}'", pages));
+    };
+
+    let start: u64 = cap[2].parse().unwrap_or(0);
+    let end: u64 = cap[5].parse().unwrap_or(0);
+
+    if end < start {
+        return Some(format!("page range '{}' ends before it starts", pages));
+    }
+
+    if &cap[3] != "--" {
+        return Some(format!("page range '{}' should use '--'", pages));
+    }
+
+    None
+}
+
+fn current_year() -> u16 {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    // 365.2425 days per year is precise enough for a plausibility check
+    (1970 + secs / 31_556_952) as u16
+}
+
+// ============================================================
+// OUTPUT
+// ============================================================
+
+fn print_report(report: &Report) {
+    let mut current: Option<&str> = None;
+
+    for issue in &report.issues {
+        if current != Some(issue.id.as_str()) {
+            println!("{}", issue.id.bold());
+            current = Some(issue.id.as_str());
+        }
+
+        let label = match issue.severity {
+            Severity::Error => "error".red().bold(),
+            Severity::Warning => "warning".yellow(),
+        };
+
+        println!(
+            "  {} {} {}",
+            label,
+            issue.field.dimmed(),
+            issue.message
+        );
+    }
+
+    if !report.issues.is_empty() {
+        println!();
+    }
+
+    let summary = format!(
+        "Checked {} references: {} error(s), {} warning(s)",
+        report.checked, report.errors, report.warnings
+    );
+
+    if report.errors > 0 {
+        println!("{}", format!("❌ {}", summary).red().bold());
+    } else if report.warnings > 0 {
+        println!("{}", format!("⚠️  {}", summary).yellow());
+    } else {
+        println!("{}", format!("✔️  {}", summary).bright_green().bold());
+    }
+}
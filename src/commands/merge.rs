@@ -0,0 +1,154 @@
+use colored::*;
+use std::fs;
+use std::io::{stdin, stdout, Write};
+
+use crate::commands::dedupe::print_side_by_side;
+use crate::commands::edit::update_all_libraries;
+use crate::reference::Reference;
+use crate::reference_store::{load_ref, ref_path, save_ref};
+use crate::utils::resolve::{print_resolve_error, resolve_reference};
+
+/// Entry point for `eln merge <keep> <drop>`
+///
+/// Folds `drop` into `keep`: attachments, tags and notes are combined;
+/// an empty title, year, author or editor list, venue field, identifier
+/// or extra field on `keep` is taken from `drop`; every library that
+/// pinned `drop` now pins `keep`, and `drop`'s ID is kept as an alias.
+pub fn run_merge(keep: String, drop: String) {
+    let (keep_id, drop_id) = match (resolve_reference(&keep), resolve_reference(&drop)) {
+        (Ok(k), Ok(d)) => (k, d),
+        (Err(e), _) | (_, Err(e)) => {
+            print_resolve_error(e);
+            return;
+        }
+    };
+
+    if keep_id == drop_id {
+        eprintln!(
+            "{}",
+            "❌ Cannot merge a reference into itself".red().bold()
+        );
+        return;
+    }
+
+    let (Some(mut kept), Some(dropped)) = (load_ref(&keep_id), load_ref(&drop_id)) else {
+        eprintln!(
+            "{}",
+            "❌ Failed to load references".red().bold()
+        );
+        return;
+    };
+
+    print_side_by_side(&kept, &dropped);
+
+    if !confirm(&format!("Merge '{}' into '{}'?", drop_id, keep_id)) {
+        println!("{}", "❌ Merge aborted.".red());
+        return;
+    }
+
+    merge_into(&mut kept, dropped);
+    save_ref(&kept);
+
+    fs::remove_file(ref_path(&drop_id))
+        .expect("❌ Failed to delete merged reference file");
+
+    update_all_libraries(&drop_id, &keep_id);
+
+    println!(
+        "{}",
+        format!(
+            "🔀 Merged '{}' into '{}' ('{}' kept as alias)",
+            drop_id, keep_id, drop_id
+        )
+        .bright_green()
+        .bold()
+    );
+}
+
+
+fn merge_into(keep: &mut Reference, drop: Reference) {
+    // --- Union ------------------------------------------------------------
+
+    for a in drop.attachments {
+        if !keep.attachments.contains(&a) {
+            keep.attachments.push(a);
+        }
+    }
+
+    for t in drop.tags {
+        if !keep.tags.contains(&t) {
+            keep.tags.push(t);
+        }
+    }
+
+    keep.notes = match (keep.notes.take(), drop.notes) {
+        (Some(a), Some(b)) if a != b => Some(format!("{}\n\n{}", a, b)),
+        (a, b) => a.or(b),
+    };
+
+    // --- Fill gaps --------------------------------------------------------
+
+    if keep.title.trim().is_empty() {
+        keep.title = drop.title;
+    }
+    keep.year = keep.year.or(drop.year);
+
+    if keep.authors.is_empty() {
+        keep.authors = drop.authors;
+    }
+    if keep.editors.is_empty() {
+        keep.editors = drop.editors;
+    }
+
+    match (&mut keep.venue, drop.venue) {
+        (Some(v), Some(other)) => {
+            v.journal = v.journal.take().or(other.journal);
+            v.booktitle = v.booktitle.take().or(other.booktitle);
+            v.publisher = v.publisher.take().or(other.publisher);
+            v.series = v.series.take().or(other.series);
+            v.volume = v.volume.take().or(other.volume);
+            v.issue = v.issue.take().or(other.issue);
+            v.pages = v.pages.take().or(other.pages);
+            v.address = v.address.take().or(other.address);
+            v.school = v.school.take().or(other.school);
+            v.institution = v.institution.take().or(other.institution);
+            v.entry_type = v.entry_type.take().or(other.entry_type);
+        }
+        (venue @ None, other) => *venue = other,
+        _ => {}
+    }
+
+    let ids = &mut keep.identifiers;
+    let other = drop.identifiers;
+    ids.doi = ids.doi.take().or(other.doi);
+    ids.arxiv = ids.arxiv.take().or(other.arxiv);
+    ids.arxiv_class = ids.arxiv_class.take().or(other.arxiv_class);
+    ids.isbn = ids.isbn.take().or(other.isbn);
+    ids.url = ids.url.take().or(other.url);
+    ids.pmid = ids.pmid.take().or(other.pmid);
+    ids.pmcid = ids.pmcid.take().or(other.pmcid);
+
+    for (k, v) in drop.extra {
+        keep.extra.entry(k).or_insert(v);
+    }
+
+    // --- Aliases ----------------------------------------------------------
+
+    for alias in std::iter::once(drop.id).chain(drop.aliases) {
+        if alias != keep.id && !keep.aliases.contains(&alias) {
+            keep.aliases.push(alias);
+        }
+    }
+}
+
+
+fn confirm(msg: &str) -> bool {
+    print!("{} [Y/n]: ", msg);
+    stdout().flush().unwrap();
+
+    let mut input = String::new();
+    stdin().read_line(&mut input).unwrap();
+    let v = input.trim().to_lowercase();
+
+    v.is_empty() || v == "y" || v == "yes"
+}
@@ -11,4 +11,13 @@ pub mod search;
 pub mod purge;
 pub mod attach;
 pub mod detach;
-pub mod open;
\ No newline at end of file
+pub mod open;
+pub mod lint;
+pub mod export;
+pub mod import;
+pub mod rekey;
+pub mod dedupe;
+pub mod merge;
+pub mod rename_key;
+pub mod cited;
+pub mod check_tex;
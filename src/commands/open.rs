@@ -54,10 +54,9 @@ fn prompt_index(max: usize) -> usize {
         let mut input = String::new();
         stdin().read_line(&mut input).unwrap();
 
-        if let Ok(n) = input.trim().parse::<usize>() {
-            if n >= 1 && n <= max {
-                return n - 1;
-            }
+        if let Ok(n) = input.trim().parse::<usize>()
+            && n >= 1 && n <= max {
+            return n - 1;
         }
 
         println!("{}", "Invalid selection".yellow());
@@ -74,10 +73,7 @@ fn open_path(path: &str) {
             .args(["/C", "start", "", path])
             .spawn()
     } else {
-        Err(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            "Unsupported OS",
-        ))
+        Err(std::io::Error::other("Unsupported OS"))
     };
 
     if result.is_err() {
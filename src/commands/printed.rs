@@ -1,10 +1,21 @@
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
 
 use colored::*;
+use notify::{EventKind, RecursiveMode, Watcher};
 use serde::Deserialize;
 
-use crate::reference::Reference;
+use crate::backup::write_atomic;
+use crate::bibtex::latex::{encode, is_verbatim, Escape};
+use crate::bibtex::{month_macro, month_number};
+use crate::bibtex::names::join_names;
+use crate::commands::purge::collect_tex_keys;
+use crate::project_store::load_all_projects;
+use crate::reference::{RefKind, Reference};
+use crate::reference_store::{load_all_refs, ref_path};
 
 /// Structure of `.elaine/index.yaml`
 #[derive(Debug, Deserialize)]
@@ -20,12 +31,331 @@ struct ProjectFile {
     #[allow(dead_code)]
     title: Option<String>,
     refs: Vec<String>,
+    #[serde(default)]
+    preambles: Vec<String>,
+    #[serde(default)]
+    output: Option<String>,
 }
 
 
 
+/// Fields written from `Identifiers.arxiv`; stale copies in `extra` are skipped
+const EPRINT_FIELDS: [&str; 5] = [
+    "eprint", "archiveprefix", "eprinttype", "primaryclass", "eprintclass",
+];
+
+
+/// Output flavour for `eln printed --dialect`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    /// Classic BibTeX fields (`journal`, `address`, `year`)
+    Bibtex,
+    /// biber / biblatex fields (`journaltitle`, `location`, `date`, `eprint`)
+    Biblatex,
+}
+
+impl Dialect {
+    pub fn parse(s: &str) -> Dialect {
+        match s {
+            "biblatex" => Dialect::Biblatex,
+            _ => Dialect::Bibtex,
+        }
+    }
+
+    /// biber reads UTF-8; classic BibTeX under pdflatex wants ASCII
+    pub fn default_escape(self) -> Escape {
+        match self {
+            Dialect::Bibtex => Escape::Latex,
+            Dialect::Biblatex => Escape::Utf8,
+        }
+    }
+}
+
+
+/// References picked by `--all`, explicit libraries, or the active library.
+pub struct Selection {
+    pub project_ids: Vec<String>,
+    pub refs: Vec<Reference>,
+    pub preambles: Vec<String>,
+    /// Output path stored on the library, when exactly one is selected
+    pub output: Option<String>,
+}
+
+
+/// Where `eln printed` sends the bibliography
+pub struct OutputTarget {
+    /// `-o`: file path overriding the library's stored one
+    pub path: Option<String>,
+    /// Print only, write no file
+    pub stdout: bool,
+    /// Write only, print nothing
+    pub quiet: bool,
+    /// Keep rewriting the file as the inputs change
+    pub watch: bool,
+}
+
+
 /// Entry point for `eln printed`
-pub fn run_printed(all: bool, projects: Vec<String>) {
+pub fn run_printed(
+    all: bool,
+    projects: Vec<String>,
+    dialect: String,
+    escape: Option<String>,
+    with_aliases: bool,
+    for_tex: Option<String>,
+    target: OutputTarget,
+) {
+    let OutputTarget { path: output, stdout, quiet, watch } = target;
+
+    let dialect = Dialect::parse(&dialect);
+    let escape = escape
+        .as_deref()
+        .map(Escape::parse)
+        .unwrap_or(dialect.default_escape());
+
+    let job = PrintJob {
+        all,
+        projects,
+        for_tex,
+        with_aliases,
+        dialect,
+        escape,
+        output,
+        quiet,
+    };
+
+    if watch {
+        watch_printed(&job);
+        return;
+    }
+
+    let Some((selection, out_name)) = job.select() else {
+        return;
+    };
+
+    let out = render_bibliography(&selection.refs, &selection.preambles, dialect, escape);
+
+    // --- Render to stdout ----------------------------------------------------
+
+    if stdout || !quiet {
+        print!("{}", out);
+    }
+
+    if stdout {
+        return;
+    }
+
+    // --- Render to file ------------------------------------------------------
+
+    if let Err(e) = write_bib(Path::new(&out_name), &out) {
+        eprintln!("{} {} ({})", "❌ Failed writing".red().bold(), out_name, e);
+        std::process::exit(1);
+    }
+
+    if !quiet {
+        println!(
+            "{}",
+            format!(
+                "🖨️  Printed {} references to → {}",
+                selection.refs.len(),
+                out_name
+            )
+            .green()
+            .bold()
+        );
+    }
+}
+
+
+/// One `eln printed` invocation, re-run on every change under `--watch`.
+struct PrintJob {
+    all: bool,
+    projects: Vec<String>,
+    for_tex: Option<String>,
+    with_aliases: bool,
+    dialect: Dialect,
+    escape: Escape,
+    /// `-o`, overriding the library's stored path and the default name
+    output: Option<String>,
+    quiet: bool,
+}
+
+impl PrintJob {
+    /// The references to print, in output order, and the output path.
+    fn select(&self) -> Option<(Selection, String)> {
+        let (mut selection, default_name) = match &self.for_tex {
+            Some(dir) => {
+                let root = Path::new(dir);
+                (select_for_tex(root)?, tex_output_name(root))
+            }
+            None => {
+                let s = select_references(self.all, self.projects.clone())?;
+                let name = output_name(&s.project_ids, self.all);
+                (s, name)
+            }
+        };
+
+        let out_name = self
+            .output
+            .clone()
+            .or_else(|| selection.output.clone())
+            .unwrap_or(default_name);
+
+        // --- Deterministic ordering -----------------------------------------
+        sort_references(&mut selection.refs);
+
+        if self.for_tex.is_some() {
+            // Already narrowed to the aliases the manuscript cites
+        } else if self.with_aliases {
+            drop_live_aliases(&mut selection.refs);
+        } else {
+            for r in &mut selection.refs {
+                r.aliases.clear();
+            }
+        }
+
+        Some((selection, out_name))
+    }
+}
+
+
+/// `eln printed --watch`: rebuild whenever a reference, a library, the
+/// active library or (with `--for-tex`) a `.tex` file changes. The file
+/// is only rewritten when its contents would differ, so viewers such as
+/// `latexmk -pvc` don't rebuild for nothing.
+fn watch_printed(job: &PrintJob) {
+    let elaine_dir = Path::new(".elaine");
+
+    if !elaine_dir.exists() {
+        eprintln!(
+            "{}",
+            "❌ .elaine/ directory not found. Run `eln init` first."
+                .red()
+                .bold()
+        );
+        return;
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(w) => w,
+        Err(e) => {
+            eprintln!("{} ({})", "❌ Failed to start file watcher".red().bold(), e);
+            return;
+        }
+    };
+
+    // `.elaine/` itself for index.yaml (the active library)
+    let mut targets = vec![
+        (elaine_dir.to_path_buf(), RecursiveMode::NonRecursive),
+        (elaine_dir.join("refs"), RecursiveMode::NonRecursive),
+        (elaine_dir.join("projects"), RecursiveMode::NonRecursive),
+    ];
+    if let Some(dir) = &job.for_tex {
+        targets.push((Path::new(dir).to_path_buf(), RecursiveMode::Recursive));
+    }
+
+    for (path, mode) in &targets {
+        if let Err(e) = watcher.watch(path, *mode) {
+            eprintln!(
+                "{} {} ({})",
+                "❌ Cannot watch".red().bold(),
+                path.display(),
+                e
+            );
+            return;
+        }
+    }
+
+    if !job.quiet {
+        println!(
+            "{}",
+            format!(
+                "👀 Watching {} (Ctrl-C to stop)",
+                targets
+                    .iter()
+                    .map(|(p, _)| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+            .bold()
+        );
+    }
+
+    rebuild_if_changed(job);
+
+    while let Ok(event) = rx.recv() {
+        match event {
+            Ok(event) if is_relevant(&event) => {}
+            Ok(_) => continue,
+            Err(e) => {
+                eprintln!("{} ({})", "⚠️  Watcher error".yellow(), e);
+                continue;
+            }
+        }
+
+        // Editors and `eln` itself write in bursts; settle first
+        while rx.recv_timeout(WATCH_SETTLE).is_ok() {}
+
+        rebuild_if_changed(job);
+    }
+}
+
+
+/// Quiet period after a change before rebuilding
+const WATCH_SETTLE: Duration = Duration::from_millis(200);
+
+/// Writes to `.yaml` (registry) or `.tex` files; reads and our own
+/// `.bib` output are ignored.
+fn is_relevant(event: &notify::Event) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) && event.paths.iter().any(|p| {
+        matches!(p.extension().and_then(|s| s.to_str()), Some("yaml" | "tex"))
+    })
+}
+
+
+fn rebuild_if_changed(job: &PrintJob) {
+    let Some((selection, out_name)) = job.select() else {
+        return;
+    };
+
+    let out = render_bibliography(&selection.refs, &selection.preambles, job.dialect, job.escape);
+
+    if fs::read_to_string(&out_name).is_ok_and(|current| current == out) {
+        if !job.quiet {
+            println!("{}", format!("· {} unchanged", out_name).dimmed());
+        }
+        return;
+    }
+
+    match write_bib(Path::new(&out_name), &out) {
+        Ok(()) if job.quiet => {}
+        Ok(()) => println!(
+            "{}",
+            format!(
+                "🖨️  Printed {} references to → {}",
+                selection.refs.len(),
+                out_name
+            )
+            .green()
+            .bold()
+        ),
+        Err(e) => eprintln!(
+            "{} {} ({})",
+            "❌ Failed writing".red().bold(),
+            out_name,
+            e
+        ),
+    }
+}
+
+
+/// Library selection shared by every command that mirrors `eln printed`.
+/// Problems are reported to stderr; `None` means there is nothing to do.
+pub fn select_references(all: bool, projects: Vec<String>) -> Option<Selection> {
     let elaine_dir = Path::new(".elaine");
 
     // --- Sanity checks ------------------------------------------------------
@@ -37,7 +367,7 @@ pub fn run_printed(all: bool, projects: Vec<String>) {
                 .red()
                 .bold()
         );
-        return;
+        return None;
     }
 
     // --- Load index ---------------------------------------------------------
@@ -52,7 +382,7 @@ pub fn run_printed(all: bool, projects: Vec<String>) {
                     .red()
                     .bold()
             );
-            return;
+            return None;
         }
     };
 
@@ -64,43 +394,152 @@ pub fn run_printed(all: bool, projects: Vec<String>) {
                 "❌ Failed to parse .elaine/index.yaml".red().bold(),
                 e.to_string().dimmed()
             );
-            return;
+            return None;
         }
     };
 
-    let project_ids = match resolve_project_ids(
+    let project_ids = resolve_project_ids(
         elaine_dir,
         all,
         projects,
         &index,
-    ) {
-        Some(v) => v,
-        None => return,
-    };
-
+    )?;
 
-    let ref_ids = match collect_reference_ids(
+    let (ref_ids, preambles, output) = collect_reference_ids(
         elaine_dir,
         &project_ids,
-    ) {
-        Some(v) => v,
-        None => return,
-    };
-
+    )?;
 
-    let mut refs = match load_references(
+    let refs = load_references(
         elaine_dir,
         &ref_ids,
-    ) {
-        Some(v) => v,
-        None => return,
-    };
+    )?;
+
+    Some(Selection {
+        project_ids,
+        refs,
+        preambles,
+        output,
+    })
+}
+
+
+/// The references a manuscript cites, looked up by ID or alias across
+/// the whole registry. Each reference keeps only the aliases that are
+/// cited, so old keys in the `.tex` still resolve.
+pub fn select_for_tex(root: &Path) -> Option<Selection> {
+    if !root.exists() {
+        eprintln!("{}", "❌ Provided path does not exist".red());
+        return None;
+    }
+
+    if !Path::new(".elaine").exists() {
+        eprintln!(
+            "{}",
+            "❌ .elaine/ directory not found. Run `eln init` first."
+                .red()
+                .bold()
+        );
+        return None;
+    }
+
+    let cited = collect_tex_keys(root);
+
+    if cited.contains("*") {
+        eprintln!(
+            "{}",
+            "⚠️  \\nocite{*} ignored; use `eln printed --all` for the whole registry".yellow()
+        );
+    }
+
+    let mut keys: Vec<&String> = cited.iter().filter(|k| *k != "*").collect();
+    keys.sort();
+
+    let all_refs = load_all_refs();
+
+    let mut by_id: HashMap<&str, usize> = HashMap::new();
+    let mut by_alias: HashMap<&str, usize> = HashMap::new();
+    for (i, r) in all_refs.iter().enumerate() {
+        by_id.insert(r.id.as_str(), i);
+        for alias in &r.aliases {
+            by_alias.insert(alias.as_str(), i);
+        }
+    }
+
+    // Reference index → cited aliases
+    let mut chosen: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    let mut unresolved: Vec<&String> = Vec::new();
+
+    for key in keys {
+        if let Some(&i) = by_id.get(key.as_str()) {
+            chosen.entry(i).or_default();
+        } else if let Some(&i) = by_alias.get(key.as_str()) {
+            chosen.entry(i).or_default().push(key.clone());
+        } else {
+            unresolved.push(key);
+        }
+    }
+
+    if !unresolved.is_empty() {
+        eprintln!(
+            "{}",
+            format!("⚠️  {} cited key(s) not in the registry:", unresolved.len()).yellow()
+        );
+        for key in &unresolved {
+            eprintln!("  {}", key);
+        }
+        eprintln!(
+            "  {}",
+            format!("→ eln check-tex {} for suggestions", root.display()).cyan()
+        );
+    }
+
+    let refs: Vec<Reference> = all_refs
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, mut r)| {
+            let aliases = chosen.remove(&i)?;
+            r.aliases = aliases;
+            Some(r)
+        })
+        .collect();
 
+    if refs.is_empty() {
+        eprintln!(
+            "{}",
+            "❌ None of the cited keys are in the registry."
+                .red()
+                .bold()
+        );
+        return None;
+    }
 
-    // --- Deterministic ordering ---------------------------------------------
-    sort_references(&mut refs);
+    // Preambles of every library holding one of the references
+    let mut projects = load_all_projects();
+    projects.sort_by(|a, b| a.id.cmp(&b.id));
 
-    render_and_write_bibtex(&refs, &project_ids, all);
+    let mut project_ids = Vec::new();
+    let mut preambles: Vec<String> = Vec::new();
+
+    for p in projects {
+        if !p.refs.iter().any(|id| refs.iter().any(|r| &r.id == id)) {
+            continue;
+        }
+
+        for pre in p.preambles {
+            if !preambles.contains(&pre) {
+                preambles.push(pre);
+            }
+        }
+        project_ids.push(p.id);
+    }
+
+    Some(Selection {
+        project_ids,
+        refs,
+        preambles,
+        output: None,
+    })
 }
 
 
@@ -129,10 +568,9 @@ fn resolve_project_ids(
 
         for entry in entries.flatten() {
             let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("yaml") {
-                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                    ids.push(stem.to_string());
-                }
+            if path.extension().and_then(|s| s.to_str()) == Some("yaml")
+                && let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                ids.push(stem.to_string());
             }
         }
 
@@ -155,13 +593,18 @@ fn resolve_project_ids(
 }
 
 
+/// Union of reference IDs across projects, plus their `@preamble`
+/// blocks (deduplicated, in project order) and, for a single project,
+/// its stored output path.
 fn collect_reference_ids(
     elaine_dir: &Path,
     project_ids: &[String],
-) -> Option<std::collections::HashSet<String>> {
+) -> Option<(std::collections::HashSet<String>, Vec<String>, Option<String>)> {
     use std::collections::HashSet;
 
     let mut ref_ids: HashSet<String> = HashSet::new();
+    let mut preambles: Vec<String> = Vec::new();
+    let mut output: Option<String> = None;
 
     for pid in project_ids {
         let project_path = elaine_dir
@@ -196,6 +639,16 @@ fn collect_reference_ids(
         for rid in project.refs {
             ref_ids.insert(rid);
         }
+
+        for pre in project.preambles {
+            if !preambles.contains(&pre) {
+                preambles.push(pre);
+            }
+        }
+
+        if project_ids.len() == 1 {
+            output = project.output;
+        }
     }
 
     if ref_ids.is_empty() {
@@ -207,7 +660,7 @@ fn collect_reference_ids(
         return None;
     }
 
-    Some(ref_ids)
+    Some((ref_ids, preambles, output))
 }
 
 
@@ -263,54 +716,68 @@ fn load_references(
 }
 
 
-fn render_and_write_bibtex(
-    refs: &[Reference],
-    project_ids: &[String],
-    all: bool,
-) {
-    // --- Render to stdout --------------------------------------------------
-
-    for r in refs {
-        print!("{}", render_bibtex(r));
-        println!();
-    }
-
-    // --- Determine output filename ----------------------------------------
-
-    let out_name = if all {
+/// `global_references.bib` for `--all`, else `<lib1>+<lib2>_references.bib`
+fn output_name(project_ids: &[String], all: bool) -> String {
+    if all {
         "global_references.bib".to_string()
     } else {
         format!(
             "{}_references.bib",
             project_ids.join("+")
         )
+    }
+}
+
+/// `paper/` → `paper_references.bib`, `main.tex` → `main_references.bib`
+fn tex_output_name(root: &Path) -> String {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+
+    let stem = if root.is_file() {
+        root.file_stem()
+    } else {
+        root.file_name()
     };
 
-    // --- Render to file ----------------------------------------------------
+    format!(
+        "{}_references.bib",
+        stem.and_then(|s| s.to_str()).unwrap_or("tex")
+    )
+}
+
+
+/// Write the `.bib`, creating its folder (`-o build/refs.bib`) if needed.
+fn write_bib(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+
+    write_atomic(path, contents)
+}
+
 
+/// The whole `.bib` file: preambles, then entries.
+fn render_bibliography(
+    refs: &[Reference],
+    preambles: &[String],
+    dialect: Dialect,
+    escape: Escape,
+) -> String {
     let mut out = String::new();
+    for pre in preambles {
+        out.push_str(&render_preamble(pre));
+        out.push('\n');
+    }
     for r in refs {
-        out.push_str(&render_bibtex(r));
+        out.push_str(&render_entry(r, dialect, escape));
         out.push('\n');
     }
-
-    fs::write(&out_name, out)
-        .expect("Failed writing BibTeX file");
-
-    println!(
-        "{}",
-        format!(
-            "🖨️  Printed {} references to → {}",
-            refs.len(),
-            out_name
-        )
-        .green()
-        .bold()
-    );
+    out
 }
 
 
-fn sort_references(refs: &mut Vec<Reference>) {
+pub fn sort_references(refs: &mut [Reference]) {
     refs.sort_by(|a, b| {
         // Year (None last)
         match (&a.year, &b.year) {
@@ -321,9 +788,9 @@ fn sort_references(refs: &mut Vec<Reference>) {
         }
         // First author
         .then_with(|| {
-            let a_author = a.authors.first().map(|s| s.as_str()).unwrap_or("");
-            let b_author = b.authors.first().map(|s| s.as_str()).unwrap_or("");
-            a_author.cmp(b_author)
+            let a_author = a.authors.first().map(|n| n.sort_key()).unwrap_or_default();
+            let b_author = b.authors.first().map(|n| n.sort_key()).unwrap_or_default();
+            a_author.cmp(&b_author)
         })
         // ID (always present)
         .then_with(|| a.id.cmp(&b.id))
@@ -331,26 +798,76 @@ fn sort_references(refs: &mut Vec<Reference>) {
 }
 
 
-fn render_bibtex(r: &Reference) -> String {
+/// An alias that is also the ID of a live reference would define the
+/// key twice; such aliases are left out with a warning.
+fn drop_live_aliases(refs: &mut [Reference]) {
+    for r in refs {
+        r.aliases.retain(|alias| {
+            let live = ref_path(alias).exists();
+            if live {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "⚠️  Alias '{}' of '{}' is itself a reference ID; not emitted",
+                        alias, r.id
+                    )
+                    .yellow()
+                );
+            }
+            !live
+        });
+    }
+}
+
+
+/// The entry plus its aliases: biblatex lists them in `ids`; classic
+/// BibTeX has no alias mechanism, so each alias gets a copy of the entry.
+fn render_entry(r: &Reference, dialect: Dialect, escape: Escape) -> String {
+    let entry = render_bibtex(r, dialect, escape);
+
+    if dialect == Dialect::Biblatex {
+        return entry;
+    }
+
+    let mut out = entry.clone();
+    let key = format!("{{{},", r.id);
+
+    for alias in &r.aliases {
+        out.push('\n');
+        out.push_str(&entry.replacen(&key, &format!("{{{},", alias), 1));
+    }
+
+    out
+}
+
+
+fn render_preamble(content: &str) -> String {
+    format!("@PREAMBLE{{{{{}}}}}\n", content)
+}
+
+
+fn render_bibtex(r: &Reference, dialect: Dialect, escape: Escape) -> String {
     let mut out = String::new();
+    let biblatex = dialect == Dialect::Biblatex;
 
     // --- Entry header -----------------------------------------------------
-    let kind = match r.kind {
-        crate::reference::RefKind::Article => "ARTICLE",
-        crate::reference::RefKind::InProceedings => "INPROCEEDINGS",
-        crate::reference::RefKind::InCollection => "INCOLLECTION",
-        crate::reference::RefKind::InBook => "INBOOK",
-        crate::reference::RefKind::Book => "BOOK",
-        crate::reference::RefKind::Misc => "MISC",
+    let kind = match dialect {
+        Dialect::Bibtex => r.kind.bibtex_name(),
+        Dialect::Biblatex => biblatex_type(r),
     };
 
-    out.push_str(&format!("@{}{{{},\n", kind, r.id));
+    out.push_str(&format!("@{}{{{},\n", kind.to_uppercase(), r.id));
+
+    if biblatex && !r.aliases.is_empty() {
+        out.push_str(&format!("  {:<8} = {{{}}},\n", "ids", r.aliases.join(", ")));
+    }
 
     // --- Helper macro -----------------------------------------------------
     macro_rules! field {
         ($name:expr, $val:expr) => {
             if let Some(v) = $val {
-                out.push_str(&format!("  {:<9}= {{{}}},\n", $name, v));
+                let v = if is_verbatim($name) { v.to_string() } else { encode(v, escape) };
+                out.push_str(&format!("  {:<8} = {{{}}},\n", $name, v));
             }
         };
     }
@@ -360,41 +877,125 @@ fn render_bibtex(r: &Reference) -> String {
 
     if !r.authors.is_empty() {
         out.push_str(&format!(
-            "  {:<9}= {{{}}},\n",
+            "  {:<8} = {{{}}},\n",
             "author",
-            r.authors.join(" and ")
+            encode(&join_names(&r.authors), escape)
         ));
     }
 
     if !r.editors.is_empty() {
         out.push_str(&format!(
-            "  {:<9}= {{{}}},\n",
+            "  {:<8} = {{{}}},\n",
             "editor",
-            r.editors.join(" and ")
+            encode(&join_names(&r.editors), escape)
         ));
     }
 
-    field!("year", r.year.map(|y| y.to_string()).as_deref());
+    // biblatex folds `month` into an ISO 8601 `date`
+    let month = r.extra.get("month").and_then(|m| month_number(m));
+
+    if biblatex {
+        let date = r.year.map(|y| match month {
+            Some(m) => format!("{:04}-{:02}", y, m),
+            None => format!("{:04}", y),
+        });
+        field!("date", date.as_deref());
+    } else {
+        field!("year", r.year.map(|y| y.to_string()).as_deref());
+    }
 
     // --- Venue fields -----------------------------------------------------
     if let Some(v) = &r.venue {
-        field!("journal", v.journal.as_deref());
+        if biblatex {
+            field!("journaltitle", v.journal.as_deref());
+        } else {
+            field!("journal", v.journal.as_deref());
+        }
         field!("booktitle", v.booktitle.as_deref());
         field!("publisher", v.publisher.as_deref());
         field!("series", v.series.as_deref());
         field!("volume", v.volume.as_deref());
         field!("number", v.issue.as_deref());
         field!("pages", v.pages.as_deref());
-        field!("address", v.address.as_deref());
+
+        if biblatex {
+            field!("location", v.address.as_deref());
+            // biblatex has a single `institution` field for theses and reports
+            field!("institution", v.school.as_deref().or(v.institution.as_deref()));
+            field!("type", v.entry_type.as_deref().or(match r.kind {
+                RefKind::PhdThesis => Some("phdthesis"),
+                RefKind::MastersThesis => Some("mathesis"),
+                RefKind::TechReport => Some("techreport"),
+                _ => None,
+            }));
+        } else {
+            field!("address", v.address.as_deref());
+            field!("school", v.school.as_deref());
+            field!("institution", v.institution.as_deref());
+            field!("type", v.entry_type.as_deref());
+        }
     }
 
     // --- Identifiers ------------------------------------------------------
     field!("doi", r.identifiers.doi.as_deref());
     field!("url", r.identifiers.url.as_deref());
     field!("isbn", r.identifiers.isbn.as_deref());
+    field!("pmid", r.identifiers.pmid.as_deref());
+    field!("pmcid", r.identifiers.pmcid.as_deref());
+
+    if r.identifiers.arxiv.is_some() {
+        field!("eprint", r.identifiers.arxiv.as_deref());
+        if biblatex {
+            field!("eprinttype", Some("arxiv"));
+            field!("eprintclass", r.identifiers.arxiv_class.as_deref());
+        } else {
+            field!("archivePrefix", Some("arXiv"));
+            field!("primaryClass", r.identifiers.arxiv_class.as_deref());
+        }
+    }
+
+    // --- Extra fields (BTreeMap → sorted by name) ------------------------
+    for (name, value) in &r.extra {
+        if name == "month" {
+            if biblatex && month.is_some() {
+                continue;
+            }
+            // `month = feb` lets the style abbreviate or translate it
+            if let Some(abbr) = month_macro(value) {
+                out.push_str(&format!("  {:<8} = {},\n", name, abbr));
+                continue;
+            }
+        }
+        // never emit a second, conflicting eprint
+        if r.identifiers.arxiv.is_some() && EPRINT_FIELDS.contains(&name.as_str()) {
+            continue;
+        }
+        field!(name, Some(value));
+    }
 
     // --- Close entry ------------------------------------------------------
     out.push_str("}\n");
 
     out
 }
+
+
+/// Entry type under biblatex: theses and reports collapse into
+/// `@thesis` / `@report` (distinguished by `type`), and untyped web
+/// resources become `@online`.
+fn biblatex_type(r: &Reference) -> &'static str {
+    match r.kind {
+        RefKind::PhdThesis | RefKind::MastersThesis => "thesis",
+        RefKind::TechReport => "report",
+        RefKind::Misc if is_web_resource(r) => "online",
+        kind => kind.bibtex_name(),
+    }
+}
+
+fn is_web_resource(r: &Reference) -> bool {
+    let has_venue = r.venue.as_ref().is_some_and(|v| {
+        v.journal.is_some() || v.booktitle.is_some() || v.publisher.is_some()
+    });
+
+    r.identifiers.url.is_some() && !has_venue
+}
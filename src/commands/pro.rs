@@ -2,19 +2,25 @@ use colored::*;
 use std::fs;
 
 use crate::state::{elaine_dir, load_index, save_index};
-use crate::project_store::create_project_if_missing;
+use crate::project_store::{create_project_if_missing, load_project, save_project};
 use crate::utils::resolve_project::{resolve_project, print_project_resolve_error};
 
 pub fn run_pro(
     library_id: Option<String>,
     delete: bool,
     rename: bool,
+    output: Option<String>,
 ) {
     if !elaine_dir().exists() {
         eprintln!("{}", "❌ Not an Elaine project. Run `eln init` first.".red());
         return;
     }
 
+    if let Some(path) = output {
+        set_library_output(library_id, &path);
+        return;
+    }
+
     match (library_id, delete, rename) {
         (Some(name), false, true) => rename_active_library(&name),
         (Some(pid), true, false) => delete_project(&pid),
@@ -95,6 +101,47 @@ fn rename_active_library(new: &str) {
 }
 
 
+/// `eln lib [<library>] --output <path>`: default `.bib` path for
+/// `eln printed`; an empty path clears it.
+fn set_library_output(selector: Option<String>, path: &str) {
+    let pid = match selector {
+        Some(sel) => match resolve_project(&sel) {
+            Ok(p) => p,
+            Err(e) => {
+                print_project_resolve_error(e);
+                return;
+            }
+        },
+        None => match load_index().active_project {
+            Some(p) => p,
+            None => {
+                eprintln!("{}", "❌ No active library".red());
+                return;
+            }
+        },
+    };
+
+    let mut project = load_project(&pid);
+    project.output = (!path.trim().is_empty()).then(|| path.trim().to_string());
+    save_project(&project);
+
+    match &project.output {
+        Some(out) => println!(
+            "{}",
+            format!("✔️  '{}' prints to {}", pid, out)
+                .bright_green()
+                .bold()
+        ),
+        None => println!(
+            "{}",
+            format!("✔️  '{}' prints to the default {}_references.bib", pid, pid)
+                .bright_green()
+                .bold()
+        ),
+    }
+}
+
+
 fn delete_project(selector: &str) {
     let pid = match resolve_project(selector) {
         Ok(p) => p,
@@ -161,6 +208,9 @@ fn show_current_library() {
                 "You are currently sitting in library {}",
                 pid.bright_green().bold()
             );
+            if let Some(out) = load_project(pid).output {
+                println!("Printing to {}", out.bold());
+            }
             println!("{}", "↳ Rename: eln lib --rename <new_name>".dimmed());
         }
         None => {
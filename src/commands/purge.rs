@@ -1,11 +1,37 @@
 use std::collections::HashSet;
 use std::fs;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 
 use colored::*;
 use walkdir::WalkDir;
 
-pub fn run_purge(path: String, _force: bool) {
+use crate::backup::{
+    create_backup, find_backup, list_backups, load_manifest, save_manifest, write_atomic,
+};
+use crate::bibtex::parser::{parse_blocks, Block};
+use crate::bibtex::print_parse_errors;
+use crate::project_store::{load_project, project_exists, save_project};
+use crate::reference_store::{load_ref, ref_path};
+use crate::state::{elaine_dir, load_index};
+use crate::utils::resolve_project::{print_project_resolve_error, resolve_project};
+
+pub fn run_purge(
+    path: Option<String>,
+    force: bool,
+    source: String,
+    unpin: Option<Option<String>>,
+    restore: Option<String>,
+) {
+    if let Some(backup) = restore {
+        run_restore(&backup);
+        return;
+    }
+
+    let Some(path) = path else {
+        eprintln!("{}", "❌ Missing path to the LaTeX project".red());
+        return;
+    };
     let root = Path::new(&path);
 
     if !root.exists() {
@@ -13,8 +39,29 @@ pub fn run_purge(path: String, _force: bool) {
         return;
     }
 
-    println!("{}", "🔎 Scanning LaTeX files…".bold());
-    let cited_keys = collect_tex_keys(root);
+    // Backups and libraries both live in .elaine/
+    if force && !elaine_dir().exists() {
+        eprintln!(
+            "{}",
+            "❌ --force keeps a backup in .elaine/ directory, which was not found. Run `eln init` first."
+                .red()
+                .bold()
+        );
+        return;
+    }
+
+    let unpin_from = match unpin {
+        Some(selector) => match unpin_target(selector) {
+            Some(pid) => Some(pid),
+            None => return,
+        },
+        None => None,
+    };
+
+    let Some(cited) = read_cited_keys(root, &source) else {
+        return;
+    };
+    let cited_keys = cited.keys;
 
     println!(
         "{}",
@@ -22,117 +69,702 @@ pub fn run_purge(path: String, _force: bool) {
             .bright_green()
     );
 
+    if cited_keys.contains("*") {
+        println!("{}", "⚠️  \\nocite{*} found: every entry is kept".yellow());
+    }
+
     println!("{}", "📚 Scanning .bib files…".bold());
     let bib_files = collect_bib_files(root);
 
-    if bib_files.is_empty() {
+    if bib_files.is_empty() && unpin_from.is_none() {
         println!("{}", "⚠️  No .bib files found.".yellow());
         return;
     }
 
-    for bib_path in bib_files {
-        purge_single_bib(&bib_path, &cited_keys);
+    let purged: Vec<PurgedBib> = bib_files
+        .iter()
+        .filter_map(|p| purge_bib(p, &cited_keys))
+        .collect();
+
+    if !force {
+        for bib in &purged {
+            write_side_file(bib);
+        }
+
+        println!();
+        println!("{}", "✔️  Purge complete (non-destructive).".bright_green().bold());
+        return;
+    }
+
+    // --- Apply in place -------------------------------------------------------
+
+    let files: Vec<PathBuf> = purged
+        .iter()
+        .filter(|b| b.removed > 0)
+        .map(|b| b.path.clone())
+        .collect();
+
+    if files.is_empty() && unpin_from.is_none() {
+        println!("{}", "✔️  Every entry is cited; nothing to purge".bright_green().bold());
+        return;
+    }
+
+    let backup = match create_backup("purge", &files) {
+        Ok(dir) => dir,
+        Err(e) => {
+            eprintln!(
+                "{} ({})",
+                "❌ Failed to back up .bib files; nothing changed".red().bold(),
+                e
+            );
+            return;
+        }
+    };
+
+    for bib in &purged {
+        if bib.removed > 0
+            && let Err(e) = write_atomic(&bib.path, &bib.content)
+        {
+            eprintln!(
+                "{} {} ({})",
+                "❌ Failed writing".red().bold(),
+                bib.path.display(),
+                e
+            );
+            continue;
+        }
+
+        println!(
+            "{} (kept {}, removed {})",
+            bib.path.display(),
+            bib.kept.to_string().green(),
+            bib.removed.to_string().red()
+        );
+    }
+
+    if let Some(pid) = unpin_from {
+        let unpinned = unpin_uncited(&pid, &cited_keys);
+
+        if !unpinned.is_empty()
+            && let Ok(mut manifest) = load_manifest(&backup)
+        {
+            manifest.unpinned.insert(pid, unpinned);
+            if let Err(e) = save_manifest(&backup, &manifest) {
+                eprintln!("{} ({})", "⚠️  Failed to record unpinned references".yellow(), e);
+            }
+        }
     }
 
     println!();
-    println!("{}", "✔️  Purge complete (non-destructive).".bright_green().bold());
+    println!(
+        "{}",
+        format!("✔️  Purge applied. Undo with: eln purge --restore {}", file_name(&backup))
+            .bright_green()
+            .bold()
+    );
+}
+
+
+/// Library to unpin from: the one named, or the active library.
+fn unpin_target(selector: Option<String>) -> Option<String> {
+    match selector {
+        Some(sel) => match resolve_project(&sel) {
+            Ok(pid) => Some(pid),
+            Err(e) => {
+                print_project_resolve_error(e);
+                None
+            }
+        },
+        None => {
+            let active = load_index().active_project;
+            if active.is_none() {
+                eprintln!("{}", "❌ No active project set.".red());
+            }
+            active
+        }
+    }
+}
+
+
+/// Unpin every reference of `pid` that is cited neither by ID nor by
+/// alias; returns the unpinned IDs.
+fn unpin_uncited(pid: &str, cited: &HashSet<String>) -> Vec<String> {
+    if cited.contains("*") {
+        return Vec::new();
+    }
+
+    let mut project = load_project(pid);
+
+    let is_cited = |id: &String| {
+        cited.contains(id)
+            || load_ref(id).is_some_and(|r| r.aliases.iter().any(|a| cited.contains(a)))
+    };
+
+    let (kept, unpinned): (Vec<String>, Vec<String>) =
+        project.refs.drain(..).partition(is_cited);
+
+    project.refs = kept;
+
+    if !unpinned.is_empty() {
+        save_project(&project);
+    }
+
+    println!(
+        "{}",
+        format!("📍 Unpinned {} uncited reference(s) from '{}'", unpinned.len(), pid)
+            .bright_green()
+    );
+    for id in &unpinned {
+        println!("  {}", id.dimmed());
+    }
+
+    unpinned
+}
+
+
+/// Entry point for `eln purge --restore <backup>`
+///
+/// Puts back the files saved in a backup and re-pins the references
+/// a purge unpinned.
+fn run_restore(selector: &str) {
+    let Some(dir) = find_backup(selector) else {
+        eprintln!(
+            "{}",
+            format!("❌ No backup matching '{}'", selector).red().bold()
+        );
+
+        let names = list_backups();
+        if !names.is_empty() {
+            println!("{}", "Available backups:".dimmed());
+            for name in names.iter().rev() {
+                println!("  {}", name);
+            }
+        }
+        return;
+    };
+
+    let manifest = match load_manifest(&dir) {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("{} ({})", "❌ Failed to read backup manifest".red().bold(), e);
+            return;
+        }
+    };
+
+    if manifest.label != "purge" {
+        println!(
+            "{}",
+            format!(
+                "⚠️  This is a '{}' backup: only its files are restored",
+                manifest.label
+            )
+            .yellow()
+        );
+    }
+
+    for file in &manifest.files {
+        let restored = fs::read_to_string(dir.join(&file.stored))
+            .and_then(|contents| write_atomic(&file.original, &contents));
+
+        match restored {
+            Ok(()) => println!("↩️  {}", file.original.display()),
+            Err(e) => eprintln!(
+                "{} {} ({})",
+                "❌ Failed restoring".red().bold(),
+                file.original.display(),
+                e
+            ),
+        }
+    }
+
+    for (pid, refs) in &manifest.unpinned {
+        if !project_exists(pid) {
+            println!(
+                "{}",
+                format!("⚠️  Library '{}' no longer exists; not re-pinning", pid).yellow()
+            );
+            continue;
+        }
+
+        let mut project = load_project(pid);
+        let mut repinned = 0;
+
+        for id in refs {
+            if !project.refs.contains(id) && ref_path(id).exists() {
+                project.refs.push(id.clone());
+                repinned += 1;
+            }
+        }
+
+        save_project(&project);
+        println!("📍 Re-pinned {} reference(s) to '{}'", repinned, pid);
+    }
+
+    println!(
+        "{}",
+        format!("✔️  Restored backup {}", file_name(&dir))
+            .bright_green()
+            .bold()
+    );
+}
+
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
 }
 
 // ============================================================
 // TEX
 // ============================================================
 
-fn collect_tex_keys(root: &Path) -> HashSet<String> {
-    let mut keys = HashSet::new();
+/// One citation key inside a citation command.
+pub struct Citation {
+    pub key: String,
+    /// Byte range of the key as written in the source
+    pub span: Range<usize>,
+    /// 1-based line number
+    pub line: usize,
+}
 
-    for entry in WalkDir::new(root)
+/// Every `.tex` file under `root`, in a stable order.
+pub fn collect_tex_files(root: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = WalkDir::new(root)
         .into_iter()
         .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
+        .map(|e| e.into_path())
+        .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("tex"))
+        .collect();
 
-        if path.extension().and_then(|s| s.to_str()) != Some("tex") {
-            continue;
-        }
+    files.sort();
+    files
+}
 
-        let content = fs::read_to_string(path)
+pub fn collect_tex_keys(root: &Path) -> HashSet<String> {
+    let mut keys = HashSet::new();
+
+    for path in collect_tex_files(root) {
+        let content = fs::read_to_string(&path)
             .unwrap_or_default();
 
-        extract_citations_from_tex(&content, &mut keys);
+        keys.extend(scan_citations(&content).into_iter().map(|c| c.key));
     }
 
     keys
 }
 
-fn extract_citations_from_tex(content: &str, keys: &mut HashSet<String>) {
+/// Find the keys of every citation command in `content`.
+///
+/// Recognizes the natbib, biblatex and apacite families (`\citep`,
+/// `\Textcite`, `\autocite`, `\footfullcite`, `\citeNP`, `\nocite`, ...)
+/// with optional `[pre][post]` notes and the multicite forms
+/// (`\cites(pre)(post)[a]{k1}[b]{k2}`). Commented-out text is skipped.
+/// `\nocite{*}` yields the key `*`.
+pub fn scan_citations(content: &str) -> Vec<Citation> {
+    let mut citations = Vec::new();
     let bytes = content.as_bytes();
     let mut i = 0;
 
     while i < bytes.len() {
-        // Look for \cite
-        if bytes[i] == b'\\' {
-            if content[i..].starts_with("\\cite") {
-                i += 5;
-
-                // Skip optional letters (citep, citet, citealp, etc.)
-                while i < bytes.len() && bytes[i].is_ascii_alphabetic() {
-                    i += 1;
+        match bytes[i] {
+            b'%' => i = skip_comment(bytes, i),
+            b'\\' => {
+                let start = i + 1;
+                let mut end = start;
+                while end < bytes.len() && bytes[end].is_ascii_alphabetic() {
+                    end += 1;
                 }
 
-                // Skip optional *
-                if i < bytes.len() && bytes[i] == b'*' {
-                    i += 1;
+                // Control symbol (`\%`, `\\`, `\{`): skip it whole
+                if end == start {
+                    i += 2;
+                    continue;
                 }
 
-                // Skip whitespace
-                while i < bytes.len() && bytes[i].is_ascii_whitespace() {
-                    i += 1;
+                i = end;
+                if let Some(multi) = cite_command(&content[start..end]) {
+                    i = scan_arguments(content, i, multi, &mut citations);
                 }
+            }
+            _ => i += 1,
+        }
+    }
 
-                // Must now be {
-                if i < bytes.len() && bytes[i] == b'{' {
-                    i += 1;
-                    let start = i;
-                    let mut depth = 1;
-
-                    while i < bytes.len() && depth > 0 {
-                        match bytes[i] {
-                            b'{' => depth += 1,
-                            b'}' => depth -= 1,
-                            _ => {}
-                        }
-                        i += 1;
-                    }
-
-                    let end = i - 1; // exclude closing brace
-
-                    if end > start {
-                        let inside = &content[start..end];
-
-                        for raw_key in inside.split(',') {
-                            let cleaned = raw_key
-                                .trim()
-                                .replace("\\_", "_");
-
-                            if !cleaned.is_empty() {
-                                keys.insert(cleaned);
-                            }
-                        }
-                    }
-                }
+    // Line numbers in one pass (citations are in source order)
+    let mut line = 1;
+    let mut pos = 0;
+    for c in citations.iter_mut() {
+        line += bytes[pos..c.span.start].iter().filter(|&&b| b == b'\n').count();
+        pos = c.span.start;
+        c.line = line;
+    }
+
+    citations
+}
+
+/// `Some(is_multicite)` if `name` (without the backslash) is a
+/// citation command taking keys.
+fn cite_command(name: &str) -> Option<bool> {
+    const PREFIXES: &[&str] = &[
+        "", "no", "auto", "paren", "text", "foot", "footfull", "full", "smart",
+        "super", "short", "vol", "pvol", "fvol", "ftvol", "svol", "tvol", "avol",
+        "note", "pnote", "fnote",
+    ];
+    const SUFFIXES: &[&str] = &[
+        "", "t", "p", "alt", "alp", "author", "fullauthor", "year", "yearpar",
+        "num", "title", "date", "url", "talias", "palias", "A", "NP", "ANP",
+        "yearNP", "authorNP",
+    ];
+
+    // Capitalized variants (`\Citet`, `\Parencite`) take the same arguments
+    let mut chars = name.chars();
+    let first = chars.next()?;
+    let name = format!("{}{}", first.to_ascii_lowercase(), chars.as_str());
+
+    let (prefix, rest) = name.split_once("cite")?;
+    if !PREFIXES.contains(&prefix) {
+        return None;
+    }
+
+    // `\footcitetext` takes keys; natbib's `\citetext` takes free text
+    let known = |s: &str| SUFFIXES.contains(&s) || (prefix == "foot" && s == "text");
+
+    if known(rest) {
+        Some(false)
+    } else {
+        match rest.strip_suffix('s') {
+            Some(single) if known(single) => Some(true),
+            _ => None,
+        }
+    }
+}
+
+/// Parse the arguments of a citation command starting at `i` (just
+/// after its name) and return the position after them. An unterminated
+/// argument runs to the end of `content` and yields no keys.
+fn scan_arguments(content: &str, mut i: usize, multi: bool, citations: &mut Vec<Citation>) -> usize {
+    let bytes = content.as_bytes();
+
+    if i < bytes.len() && bytes[i] == b'*' {
+        i += 1;
+    }
+
+    // Multicite global notes: (pre)(post)
+    if multi {
+        for _ in 0..2 {
+            let j = skip_space(bytes, i);
+            if j < bytes.len() && bytes[j] == b'(' {
+                let Some(end) = skip_group(bytes, j, b')') else {
+                    return bytes.len();
+                };
+                i = end;
             } else {
-                i += 1;
+                break;
             }
-        } else {
-            i += 1;
         }
     }
+
+    loop {
+        // Per-citation notes: [pre][post]
+        loop {
+            let j = skip_space(bytes, i);
+            if j < bytes.len() && bytes[j] == b'[' {
+                let Some(end) = skip_group(bytes, j, b']') else {
+                    return bytes.len();
+                };
+                i = end;
+            } else {
+                break;
+            }
+        }
+
+        let j = skip_space(bytes, i);
+        if j >= bytes.len() || bytes[j] != b'{' {
+            return i;
+        }
+
+        let Some(end) = skip_group(bytes, j, b'}') else {
+            return bytes.len();
+        };
+        push_keys(content, j + 1..end - 1, citations);
+        i = end;
+
+        if !multi {
+            return i;
+        }
+    }
+}
+
+/// Skip whitespace and comments between arguments.
+fn skip_space(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => i = skip_comment(bytes, i),
+            b if b.is_ascii_whitespace() => i += 1,
+            _ => break,
+        }
+    }
+    i
+}
+
+/// Position after the newline ending the comment at `i`.
+fn skip_comment(bytes: &[u8], i: usize) -> usize {
+    bytes[i..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map(|n| i + n + 1)
+        .unwrap_or(bytes.len())
+}
+
+/// Position after the `close` matching the opener at `start`. Braces
+/// nest, so `[{see [1]}]` is one group. `None` if the group is never
+/// closed.
+fn skip_group(bytes: &[u8], start: usize, close: u8) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut i = start + 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'%' => {
+                i = skip_comment(bytes, i);
+                continue;
+            }
+            b'{' => depth += 1,
+            b'}' if depth > 0 => depth -= 1,
+            b if b == close && depth == 0 => return Some(i + 1),
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Split the comma-separated key list at `range` into citations,
+/// ignoring comments inside it and macro parameters.
+fn push_keys(content: &str, range: Range<usize>, citations: &mut Vec<Citation>) {
+    let bytes = content.as_bytes();
+    let mut key: Option<Range<usize>> = None;
+    let mut i = range.start;
+
+    let mut flush = |key: &mut Option<Range<usize>>| {
+        // `#1` is a macro parameter (`\newcommand{\mycite}[1]{\citep{#1}}`);
+        // BibTeX keys cannot contain `#`
+        if let Some(span) = key.take()
+            && !content[span.clone()].contains('#')
+        {
+            citations.push(Citation {
+                key: content[span.clone()].replace("\\_", "_"),
+                span,
+                line: 0,
+            });
+        }
+    };
+
+    while i < range.end {
+        match bytes[i] {
+            b'%' if i == 0 || bytes[i - 1] != b'\\' => {
+                i = skip_comment(bytes, i).min(range.end);
+                continue;
+            }
+            b',' => flush(&mut key),
+            b if b.is_ascii_whitespace() => {}
+            _ => match key.as_mut() {
+                Some(span) => span.end = i + 1,
+                None => key = Some(i..i + 1),
+            },
+        }
+        i += 1;
+    }
+
+    flush(&mut key);
+}
+
+// ============================================================
+// BUILD ARTIFACTS (.aux / .bcf)
+// ============================================================
+
+/// Cited keys and the files they were read from.
+pub struct CitedKeys {
+    pub keys: HashSet<String>,
+    pub files: Vec<PathBuf>,
+    /// Read from `.aux` / `.bcf` rather than scanned from `.tex`
+    pub from_build: bool,
+}
+
+/// Keys cited under `root`, by `source`:
+///
+/// * `build` — the `.aux` (`\citation`) and biber `.bcf` files of the last compile
+/// * `tex` — a scan of the `.tex` sources
+/// * `auto` — `build` when any artifact exists, otherwise `tex`
+///
+/// Returns `None` for `build` when there are no artifacts.
+pub fn collect_cited_keys(root: &Path, source: &str) -> Option<CitedKeys> {
+    if source != "tex" {
+        let artifacts = collect_build_files(root);
+
+        if !artifacts.is_empty() {
+            let mut keys = HashSet::new();
+            for path in &artifacts {
+                let content = fs::read_to_string(path).unwrap_or_default();
+
+                match path.extension().and_then(|s| s.to_str()) {
+                    Some("bcf") => keys.extend(bcf_keys(&content)),
+                    _ => keys.extend(aux_keys(&content)),
+                }
+            }
+
+            return Some(CitedKeys {
+                keys,
+                files: artifacts,
+                from_build: true,
+            });
+        }
+
+        if source == "build" {
+            return None;
+        }
+    }
+
+    let files = collect_tex_files(root);
+
+    Some(CitedKeys {
+        keys: collect_tex_keys(root),
+        files,
+        from_build: false,
+    })
+}
+
+/// [`collect_cited_keys`], telling the user where the keys came from.
+pub fn read_cited_keys(root: &Path, source: &str) -> Option<CitedKeys> {
+    let Some(cited) = collect_cited_keys(root, source) else {
+        eprintln!(
+            "{}",
+            "❌ No .aux or .bcf files found. Compile the document first, or use --source tex."
+                .red()
+                .bold()
+        );
+        return None;
+    };
+
+    if cited.from_build {
+        println!(
+            "{}",
+            format!("🔎 Reading {} build file(s) (.aux/.bcf)…", cited.files.len()).bold()
+        );
+
+        if build_is_stale(root, &cited.files) {
+            println!(
+                "{}",
+                "⚠️  .tex files changed since the last compile; recompile for an exact key list"
+                    .yellow()
+            );
+        }
+    } else {
+        println!("{}", "🔎 Scanning LaTeX files…".bold());
+    }
+
+    Some(cited)
+}
+
+/// True when a `.tex` file under `root` is newer than every build
+/// artifact, i.e. the keys from the last compile may be stale.
+fn build_is_stale(root: &Path, artifacts: &[PathBuf]) -> bool {
+    let modified = |p: &PathBuf| fs::metadata(p).and_then(|m| m.modified()).ok();
+
+    let Some(built) = artifacts.iter().filter_map(modified).max() else {
+        return false;
+    };
+
+    collect_tex_files(root)
+        .iter()
+        .filter_map(modified)
+        .any(|t| t > built)
+}
+
+fn collect_build_files(root: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|p| matches!(p.extension().and_then(|s| s.to_str()), Some("aux" | "bcf")))
+        .collect();
+
+    files.sort();
+    files
+}
+
+/// `\citation{a,b}` (BibTeX) and `\abx@aux@cite{0}{a}` (biblatex).
+/// `.aux` files are machine-written: one command per line.
+fn aux_keys(content: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim_start();
+
+        if let Some(rest) = line.strip_prefix("\\citation{") {
+            let list = rest.split('}').next().unwrap_or_default();
+            keys.extend(
+                list.split(',')
+                    .map(|k| k.trim())
+                    .filter(|k| !k.is_empty())
+                    .map(|k| k.to_string()),
+            );
+        } else if let Some(rest) = line.strip_prefix("\\abx@aux@cite") {
+            // The key is the last brace group (older biblatex has only one)
+            let key = rest
+                .split('{')
+                .filter_map(|g| g.split('}').next())
+                .rfind(|g| !g.trim().is_empty());
+
+            if let Some(key) = key {
+                keys.push(key.trim().to_string());
+            }
+        }
+    }
+
+    keys
+}
+
+/// Every `<bcf:citekey>` in a biber control file.
+fn bcf_keys(content: &str) -> Vec<String> {
+    let Ok(doc) = roxmltree::Document::parse(content) else {
+        eprintln!("{}", "⚠️  Skipping unreadable .bcf file".yellow());
+        return Vec::new();
+    };
+
+    doc.descendants()
+        .filter(|n| n.tag_name().name() == "citekey")
+        .filter_map(|n| n.text())
+        .map(|k| k.trim().to_string())
+        .filter(|k| !k.is_empty())
+        .collect()
 }
 
 // ============================================================
 // BIB
 // ============================================================
 
+/// Keys of every entry in the `.bib` files under `root`.
+pub fn collect_bib_keys(root: &Path) -> (HashSet<String>, usize) {
+    let files = collect_bib_files(root);
+    let mut keys = HashSet::new();
+
+    for path in &files {
+        let content = fs::read_to_string(path).unwrap_or_default();
+        let (blocks, _) = parse_blocks(&content);
+
+        keys.extend(blocks.into_iter().filter_map(|b| match b {
+            Block::Entry(entry) => Some(entry.key.replace("\\_", "_")),
+            _ => None,
+        }));
+    }
+
+    (keys, files.len())
+}
+
 fn collect_bib_files(root: &Path) -> Vec<PathBuf> {
     WalkDir::new(root)
         .into_iter()
@@ -145,10 +777,9 @@ fn collect_bib_files(root: &Path) -> Vec<PathBuf> {
             }
 
             // 🔥 Ignore already purged files
-            if let Some(name) = path.file_name().and_then(|s| s.to_str()) {
-                if name.ends_with("_purged.bib") {
-                    return false;
-                }
+            if let Some(name) = path.file_name().and_then(|s| s.to_str())
+                && name.ends_with("_purged.bib") {
+                return false;
             }
 
             true
@@ -157,7 +788,18 @@ fn collect_bib_files(root: &Path) -> Vec<PathBuf> {
         .collect()
 }
 
-fn purge_single_bib(path: &Path, cited: &HashSet<String>) {
+/// One `.bib` file filtered down to its cited entries
+struct PurgedBib {
+    path: PathBuf,
+    content: String,
+    kept: usize,
+    removed: usize,
+}
+
+/// Drop the uncited entries from a `.bib`. Everything outside them
+/// (`@string`, `@preamble`, comments, formatting) is copied verbatim.
+/// Files that do not parse cleanly are left alone.
+fn purge_bib(path: &Path, cited: &HashSet<String>) -> Option<PurgedBib> {
     let content = match fs::read_to_string(path) {
         Ok(c) => c,
         Err(_) => {
@@ -166,37 +808,61 @@ fn purge_single_bib(path: &Path, cited: &HashSet<String>) {
                 format!("❌ Failed to read {}", path.display())
                     .red()
             );
-            return;
+            return None;
         }
     };
 
-    let entries = parse_bib_entries(&content);
+    let (blocks, errors) = parse_blocks(&content);
 
-    let mut kept_blocks = Vec::new();
+    if !errors.is_empty() {
+        print_parse_errors(&path.display().to_string(), &errors);
+        eprintln!(
+            "{}",
+            format!("❌ Skipping {}: fix the errors above first", path.display())
+                .red()
+        );
+        return None;
+    }
+
+    let mut new_content = String::new();
+    let mut copied_to = 0;
     let mut kept_count = 0;
     let mut removed_count = 0;
 
-    for (key, block) in entries {
-        if cited.contains(&key) {
-            kept_blocks.push(block);
+    for block in blocks {
+        let Block::Entry(entry) = block else {
+            continue;
+        };
+
+        let key = entry.key.replace("\\_", "_");
+        if cited.contains("*") || cited.contains(&key) {
             kept_count += 1;
-        } else {
-            removed_count += 1;
+            continue;
         }
-    }
 
-    let output_path = purged_path(path);
+        // The entry and the blank lines after it
+        let rest = &content[entry.span.end..];
+        let end = entry.span.end + (rest.len() - rest.trim_start().len());
 
-    let mut new_content = String::new();
-    for block in kept_blocks {
-        new_content.push_str(&block);
-        if !block.ends_with('\n') {
-            new_content.push('\n');
-        }
-        new_content.push('\n');
+        new_content.push_str(&content[copied_to..entry.span.start]);
+        copied_to = end;
+        removed_count += 1;
     }
 
-    if let Err(_) = fs::write(&output_path, new_content) {
+    new_content.push_str(&content[copied_to..]);
+
+    Some(PurgedBib {
+        path: path.to_path_buf(),
+        content: new_content,
+        kept: kept_count,
+        removed: removed_count,
+    })
+}
+
+fn write_side_file(bib: &PurgedBib) {
+    let output_path = purged_path(&bib.path);
+
+    if fs::write(&output_path, &bib.content).is_err() {
         eprintln!(
             "{}",
             format!("❌ Failed writing {}", output_path.display())
@@ -207,10 +873,10 @@ fn purge_single_bib(path: &Path, cited: &HashSet<String>) {
 
     println!(
         "{} → {} (kept {}, removed {})",
-        path.file_name().unwrap().to_string_lossy(),
+        bib.path.file_name().unwrap().to_string_lossy(),
         output_path.file_name().unwrap().to_string_lossy(),
-        kept_count.to_string().green(),
-        removed_count.to_string().red()
+        bib.kept.to_string().green(),
+        bib.removed.to_string().red()
     );
 }
 
@@ -225,74 +891,80 @@ fn purged_path(original: &Path) -> PathBuf {
     parent.join(format!("{}_purged.bib", stem))
 }
 
-// ============================================================
-// ENTRY PARSER (brace depth safe)
-// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-fn parse_bib_entries(content: &str) -> Vec<(String, String)> {
-    let mut entries = Vec::new();
-    let bytes = content.as_bytes();
-    let mut i = 0;
+    fn keys(content: &str) -> Vec<String> {
+        scan_citations(content).into_iter().map(|c| c.key).collect()
+    }
 
-    while i < bytes.len() {
-        if bytes[i] == b'@' {
-            let start = i;
-            i += 1;
+    #[test]
+    fn starred_cite_with_notes() {
+        assert_eq!(keys("see \\cite*[p.~3]{a, b}."), ["a", "b"]);
+        assert_eq!(keys("\\citep[see][ch.~2]{c}"), ["c"]);
+    }
 
-            // find first '{'
-            while i < bytes.len() && bytes[i] != b'{' {
-                i += 1;
-            }
+    #[test]
+    fn multicite() {
+        assert_eq!(keys("\\textcites(pre)(post)[a]{k1}[b]{k2,k3} x"), ["k1", "k2", "k3"]);
+    }
 
-            if i >= bytes.len() {
-                break;
-            }
+    #[test]
+    fn nocite_star() {
+        assert_eq!(keys("\\nocite{*}"), ["*"]);
+    }
 
-            i += 1; // skip '{'
-            let key_start = i;
+    #[test]
+    fn commented_out_cites() {
+        assert!(keys("% \\cite{gone}\n").is_empty());
+        assert_eq!(keys("50\\% \\cite{kept}"), ["kept"]);
+        assert_eq!(keys("\\cite{a, % old\n b}"), ["a", "b"]);
+    }
 
-            while i < bytes.len() && bytes[i] != b',' {
-                i += 1;
-            }
+    #[test]
+    fn non_cite_commands_ignored() {
+        assert!(keys("\\citetext{free text} \\label{x}").is_empty());
+    }
 
-            if i >= bytes.len() {
-                break;
-            }
+    #[test]
+    fn line_numbers() {
+        let found = scan_citations("one\ntwo \\cite{a}\n\\cite{b}");
+        let lines: Vec<usize> = found.iter().map(|c| c.line).collect();
+        assert_eq!(lines, [2, 3]);
+    }
 
-            let key = content[key_start..i]
-                .trim()
-                .replace("\\_", "_");
-
-            let mut depth = 1;
-            i += 1;
-
-            while i < bytes.len() {
-                match bytes[i] {
-                    b'{' => depth += 1,
-                    b'}' => {
-                        depth -= 1;
-                        if depth == 0 {
-                            i += 1;
-                            break;
-                        }
-                    }
-                    b'@' if depth > 0 => {
-                        // 🔥 RECOVERY POINT
-                        // malformed entry — resync
-                        break;
-                    }
-                    _ => {}
-                }
-                i += 1;
-            }
+    #[test]
+    fn unterminated_notes() {
+        assert!(keys("\\cite[p.~3{a}").is_empty());
+        assert!(keys("\\cites(pre{a}").is_empty());
+    }
 
-            let end = i.min(bytes.len());
-            let block = content[start..end].to_string();
-            entries.push((key, block));
-        } else {
-            i += 1;
-        }
+    #[test]
+    fn unterminated_group_with_non_ascii_tail() {
+        assert!(keys("text \\cite{abé").is_empty());
+    }
+
+    #[test]
+    fn unterminated_group_is_not_truncated() {
+        assert_eq!(keys("\\cite{a} text \\cite{abc"), ["a"]);
     }
 
-    entries
-}
\ No newline at end of file
+    #[test]
+    fn aux_citations() {
+        let aux = "\\relax\n\\citation{a,b}\n\\citation{c}\n\\abx@aux@cite{0}{d}\n\\abx@aux@cite{e}\n";
+        assert_eq!(aux_keys(aux), ["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn bcf_citekeys() {
+        let bcf = r#"<?xml version="1.0"?>
+<bcf:controlfile xmlns:bcf="https://sourceforge.net/projects/biblatex">
+  <bcf:section number="0">
+    <bcf:citekey order="1">a</bcf:citekey>
+    <bcf:citekey order="2">*</bcf:citekey>
+  </bcf:section>
+</bcf:controlfile>"#;
+        assert_eq!(bcf_keys(bcf), ["a", "*"]);
+    }
+}
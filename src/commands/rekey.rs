@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+
+use colored::*;
+
+use crate::commands::edit::rename_reference;
+use crate::commands::printed::select_references;
+use crate::config::load_config;
+use crate::reference_store::{ref_path, save_ref};
+use crate::utils::citekey::{render_key, validate_template};
+use crate::utils::id::disambiguate;
+
+/// Entry point for `eln rekey`
+///
+/// Regenerates IDs for every reference in the selected libraries using
+/// `citekey.format`. New keys that collide with an existing reference
+/// get an `a`, `b`, ... suffix.
+pub fn run_rekey(all: bool, projects: Vec<String>, dry_run: bool) {
+    let config = load_config().citekey;
+
+    if let Some(format) = &config.format
+        && let Err(e) = validate_template(format)
+    {
+        eprintln!(
+            "{}\n{}",
+            "❌ Invalid citekey.format in .elaine/config.yaml".red().bold(),
+            e.dimmed()
+        );
+        return;
+    }
+
+    let Some(mut selection) = select_references(all, projects) else {
+        return;
+    };
+
+    selection.refs.sort_by(|a, b| a.id.cmp(&b.id));
+
+    // --- Plan -----------------------------------------------------------------
+
+    let mut planned: HashSet<String> = HashSet::new();
+    let mut renames = Vec::new();
+
+    for r in selection.refs {
+        let base = render_key(&r, &config).expect("template validated above");
+
+        let key = disambiguate(&base, |k| {
+            k != r.id && (planned.contains(k) || ref_path(k).exists())
+        });
+
+        planned.insert(key.clone());
+
+        if key != r.id {
+            renames.push((r, key));
+        }
+    }
+
+    if renames.is_empty() {
+        println!("{}", "✔️  All keys already match the template".bright_green().bold());
+        return;
+    }
+
+    for (r, key) in &renames {
+        println!("  {} → {}", r.id.dimmed(), key.bold());
+    }
+
+    let count = renames.len();
+
+    if dry_run {
+        println!(
+            "{}",
+            format!("🔍 Dry run: {} reference(s) would be renamed", count).yellow()
+        );
+        return;
+    }
+
+    // --- Apply ----------------------------------------------------------------
+
+    for (mut r, key) in renames {
+        rename_reference(&mut r, &key);
+        save_ref(&r);
+    }
+
+    println!(
+        "{}",
+        format!("🔑 Renamed {} reference(s)", count)
+            .bright_green()
+            .bold()
+    );
+}
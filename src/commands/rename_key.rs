@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use colored::*;
+
+use crate::backup::{create_backup, write_atomic};
+use crate::commands::edit::rename_reference;
+use crate::commands::purge::{collect_tex_files, scan_citations};
+use crate::reference_store::{load_ref, ref_path, save_ref};
+use crate::utils::resolve::{print_resolve_error, resolve_reference};
+
+/// A `.tex` file whose citations change
+struct TexChange {
+    path: PathBuf,
+    before: String,
+    after: String,
+    citations: usize,
+    /// 1-based, ascending, deduplicated
+    lines: Vec<usize>,
+}
+
+/// Entry point for `eln rename-key`
+///
+/// Renames the reference (atom, libraries, alias) and rewrites its
+/// citations under `tex`. Citations of the old ID and of any alias are
+/// all pointed at the new key. `.tex` files are backed up first.
+pub fn run_rename_key(old: String, new: String, tex: Option<String>, dry_run: bool) {
+    let ref_id = match resolve_reference(&old) {
+        Ok(id) => id,
+        Err(e) => {
+            print_resolve_error(e);
+            return;
+        }
+    };
+
+    let Some(mut reference) = load_ref(&ref_id) else {
+        eprintln!(
+            "{}",
+            format!("❌ Reference '{}' not found", ref_id).red().bold()
+        );
+        return;
+    };
+
+    if new.is_empty() || new.contains(|c: char| c.is_whitespace() || ",{}%#\\".contains(c)) {
+        eprintln!(
+            "{}",
+            format!("❌ '{}' is not a valid citation key", new).red().bold()
+        );
+        return;
+    }
+
+    if new != ref_id && ref_path(&new).exists() {
+        eprintln!(
+            "{}",
+            format!("❌ Reference '{}' already exists", new).red().bold()
+        );
+        return;
+    }
+
+    // --- Plan -----------------------------------------------------------------
+
+    let mut from: HashSet<String> = reference.aliases.iter().cloned().collect();
+    from.insert(ref_id.clone());
+    from.remove(&new);
+
+    let changes = match &tex {
+        Some(dir) => {
+            let root = Path::new(dir);
+            if !root.exists() {
+                eprintln!("{}", "❌ Provided path does not exist".red());
+                return;
+            }
+            plan_tex_changes(root, &from, &new)
+        }
+        None => Vec::new(),
+    };
+
+    if ref_id == new && changes.is_empty() {
+        println!("{}", format!("✔️  Nothing to rename: no citations of '{}' need updating", new).bright_green());
+        return;
+    }
+
+    if ref_id != new {
+        println!("🔑 {} → {}", ref_id.dimmed(), new.bold());
+    }
+
+    for change in &changes {
+        print_diff(change);
+    }
+
+    let citations: usize = changes.iter().map(|c| c.citations).sum();
+
+    if dry_run {
+        println!(
+            "{}",
+            format!(
+                "🔍 Dry run: {} citation(s) in {} file(s) would be rewritten",
+                citations,
+                changes.len()
+            )
+            .yellow()
+        );
+        return;
+    }
+
+    // --- Apply ----------------------------------------------------------------
+
+    if !changes.is_empty() {
+        let files: Vec<PathBuf> = changes.iter().map(|c| c.path.clone()).collect();
+
+        match create_backup("rename-key", &files) {
+            Ok(dir) => println!(
+                "{}",
+                format!("💾 Backed up {} file(s) → {}", files.len(), dir.display()).dimmed()
+            ),
+            Err(e) => {
+                eprintln!(
+                    "{} ({})",
+                    "❌ Failed to back up .tex files; nothing changed".red().bold(),
+                    e
+                );
+                return;
+            }
+        }
+    }
+
+    if ref_id != new {
+        rename_reference(&mut reference, &new);
+        save_ref(&reference);
+    }
+
+    for change in &changes {
+        if let Err(e) = write_atomic(&change.path, &change.after) {
+            eprintln!(
+                "{} {} ({})",
+                "❌ Failed writing".red().bold(),
+                change.path.display(),
+                e
+            );
+        }
+    }
+
+    println!(
+        "{}",
+        format!(
+            "✔️  '{}' is now '{}' ({} citation(s) in {} file(s) rewritten)",
+            ref_id,
+            new,
+            citations,
+            changes.len()
+        )
+        .bright_green()
+        .bold()
+    );
+}
+
+
+fn plan_tex_changes(root: &Path, from: &HashSet<String>, to: &str) -> Vec<TexChange> {
+    let files = if root.is_file() {
+        vec![root.to_path_buf()]
+    } else {
+        collect_tex_files(root)
+    };
+
+    let mut changes = Vec::new();
+
+    for path in files {
+        let Ok(before) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        let hits: Vec<_> = scan_citations(&before)
+            .into_iter()
+            .filter(|c| from.contains(&c.key))
+            .collect();
+
+        if hits.is_empty() {
+            continue;
+        }
+
+        // Replace back to front so earlier spans stay valid
+        let mut after = before.clone();
+        for c in hits.iter().rev() {
+            after.replace_range(c.span.clone(), to);
+        }
+
+        let mut lines: Vec<usize> = hits.iter().map(|c| c.line).collect();
+        lines.dedup();
+
+        changes.push(TexChange {
+            path,
+            before,
+            after,
+            citations: hits.len(),
+            lines,
+        });
+    }
+
+    changes
+}
+
+
+/// Changed lines as `-` / `+` pairs (keys never span lines).
+fn print_diff(change: &TexChange) {
+    let before: Vec<&str> = change.before.lines().collect();
+    let after: Vec<&str> = change.after.lines().collect();
+
+    for &n in &change.lines {
+        let (Some(a), Some(b)) = (before.get(n - 1), after.get(n - 1)) else {
+            continue;
+        };
+
+        println!("{}", format!("{}:{}", change.path.display(), n).cyan());
+        println!("  {}", format!("- {}", a.trim()).red());
+        println!("  {}", format!("+ {}", b.trim()).green());
+    }
+}
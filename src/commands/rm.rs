@@ -47,10 +47,9 @@ pub fn run_rm(ref_id: String) {
 
     // --- Check if reference is used elsewhere ----------------------------
 
-    if !is_ref_used_elsewhere(&ref_id, &pid) {
-        if confirm("Reference unused globally. Delete file too?") {
-            delete_ref_file(&ref_id);
-        }
+    if !is_ref_used_elsewhere(&ref_id, &pid)
+        && confirm("Reference unused globally. Delete file too?") {
+        delete_ref_file(&ref_id);
     }
 }
 
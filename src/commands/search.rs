@@ -25,7 +25,7 @@ pub fn run_search(selector: String) {
         format!(
             "🔍 Search results for: {} ({}, {})",
             r.title,
-            r.authors.get(0).unwrap_or(&"".into()),
+            r.authors.first().map(|a| a.display()).unwrap_or_default(),
             r.year.unwrap_or_default()
         )
         .bold()
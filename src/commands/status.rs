@@ -46,10 +46,9 @@ fn load_projects() -> Vec<Project> {
 
     if let Ok(entries) = fs::read_dir(&projects_dir) {
         for entry in entries.flatten() {
-            if let Ok(contents) = fs::read_to_string(entry.path()) {
-                if let Ok(p) = serde_yaml::from_str::<Project>(&contents) {
-                    projects.push(p);
-                }
+            if let Ok(contents) = fs::read_to_string(entry.path())
+                && let Ok(p) = serde_yaml::from_str::<Project>(&contents) {
+                projects.push(p);
             }
         }
     }
@@ -65,10 +64,9 @@ fn load_all_ref_ids() -> Vec<String> {
     if let Ok(entries) = fs::read_dir(&refs_dir) {
         for entry in entries.flatten() {
             let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("yaml") {
-                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                    refs.push(stem.to_string());
-                }
+            if path.extension().and_then(|s| s.to_str()) == Some("yaml")
+                && let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                refs.push(stem.to_string());
             }
         }
     }
@@ -160,7 +158,7 @@ fn print_orphans(
 }
 
 
-fn sort_refs(refs: &mut Vec<String>, sort: Option<&str>) {
+fn sort_refs(refs: &mut [String], sort: Option<&str>) {
     match sort {
         None | Some("id") => {
             refs.sort();
@@ -169,16 +167,16 @@ fn sort_refs(refs: &mut Vec<String>, sort: Option<&str>) {
             refs.sort_by(|a, b| {
                 let ra = load_ref(a);
                 let rb = load_ref(b);
-                ra.and_then(|r| Some(r.title))
-                    .cmp(&rb.and_then(|r| Some(r.title)))
+                ra.map(|r| r.title)
+                    .cmp(&rb.map(|r| r.title))
             });
         }
         Some("author") => {
             refs.sort_by(|a, b| {
                 let ra = load_ref(a);
                 let rb = load_ref(b);
-                let aa = ra.and_then(|r| r.authors.get(0).cloned()).unwrap_or_default();
-                let ab = rb.and_then(|r| r.authors.get(0).cloned()).unwrap_or_default();
+                let aa = ra.and_then(|r| r.authors.first().map(|n| n.sort_key())).unwrap_or_default();
+                let ab = rb.and_then(|r| r.authors.first().map(|n| n.sort_key())).unwrap_or_default();
                 aa.cmp(&ab)
             });
         }
@@ -218,7 +216,11 @@ fn print_ref(rid: &str, indent: &str, verbose: u8) {
             }
             _ => {
                 // identity + semantic fused
-                let author = r.authors.get(0).map(String::as_str).unwrap_or("Unknown");
+                let author = r
+                    .authors
+                    .first()
+                    .map(|n| n.display())
+                    .unwrap_or_else(|| "Unknown".into());
                 let year = r.year
                     .map(|y| y.to_string())
                     .unwrap_or_else(|| "n.d.".into());
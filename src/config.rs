@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::state::elaine_dir;
+
+/// Structure of `.elaine/config.yaml` (optional; every key has a default)
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct Config {
+    #[serde(default)]
+    pub citekey: CitekeyConfig,
+}
+
+/// Citation key generation, used by `eln add`, `eln edit`, `eln rekey`
+/// and `eln import`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CitekeyConfig {
+    /// Key template such as `{auth}{year}{title:w1:capitalize}`.
+    /// Unset keeps the built-in `smith2020deeple` scheme.
+    #[serde(default)]
+    pub format: Option<String>,
+
+    /// Title words ignored by `{title}` (case-insensitive)
+    #[serde(default)]
+    pub skip_words: Vec<String>,
+
+    /// Fold accented letters to ASCII (`Müller` → `Muller`)
+    #[serde(default = "default_transliterate")]
+    pub transliterate: bool,
+}
+
+impl Default for CitekeyConfig {
+    fn default() -> Self {
+        Self {
+            format: None,
+            skip_words: Vec::new(),
+            transliterate: default_transliterate(),
+        }
+    }
+}
+
+fn default_transliterate() -> bool {
+    true
+}
+
+pub fn config_path() -> PathBuf {
+    elaine_dir().join("config.yaml")
+}
+
+pub fn load_config() -> Config {
+    let path = config_path();
+    if !path.exists() {
+        return Config::default();
+    }
+
+    let contents = fs::read_to_string(path)
+        .expect("❌ Failed to read .elaine/config.yaml");
+
+    serde_yaml::from_str(&contents)
+        .expect("❌ Failed to parse .elaine/config.yaml")
+}
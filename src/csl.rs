@@ -0,0 +1,371 @@
+//! CSL-JSON (Citation Style Language) import and export.
+//!
+//! This is the bibliography format consumed by Pandoc and Quarto.
+//! Mapping is deliberately lossy only where CSL has no equivalent slot;
+//! everything else round-trips through `Reference`.
+
+use std::collections::BTreeMap;
+
+use colored::*;
+use serde_json::{json, Map, Value};
+
+use crate::bibtex::latex::{case_braces_to_nocase, nocase_to_case_braces};
+use crate::bibtex::{month_name, month_number};
+use crate::bibtex::names::Name;
+use crate::reference::{Identifiers, RefKind, Reference, Venue};
+use crate::utils::citekey::{imported_key, make_key};
+use crate::utils::id::make_sid;
+
+/// CSL variables copied verbatim to/from `Reference.extra`
+const EXTRA_VARIABLES: [(&str, &str); 6] = [
+    ("abstract", "abstract"),
+    ("note", "note"),
+    ("keyword", "keywords"),
+    ("ISSN", "issn"),
+    ("edition", "edition"),
+    ("language", "language"),
+];
+
+// ============================================================
+// IMPORT
+// ============================================================
+
+/// Parse a CSL-JSON document (an array of items, or a single item).
+/// Items without a title are skipped with a warning.
+pub fn parse_csl_json(input: &str) -> Result<Vec<Reference>, String> {
+    let value: Value = serde_json::from_str(input).map_err(|e| e.to_string())?;
+
+    let items = match value {
+        Value::Array(items) => items,
+        Value::Object(_) => vec![value],
+        _ => return Err("expected an array of CSL items".to_string()),
+    };
+
+    let mut refs = Vec::new();
+
+    for (i, item) in items.iter().enumerate() {
+        match item_to_reference(item) {
+            Some(r) => refs.push(r),
+            None => eprintln!(
+                "{}",
+                format!("⚠️  Skipping CSL item #{} (missing title)", i + 1).yellow()
+            ),
+        }
+    }
+
+    Ok(refs)
+}
+
+fn item_to_reference(item: &Value) -> Option<Reference> {
+    let title = title_text(item, "title")?;
+
+    let csl_type = text(item, "type").unwrap_or_default();
+    let genre = text(item, "genre");
+    let kind = kind_from_csl(&csl_type, genre.as_deref());
+
+    let authors = names(item, "author");
+    let editors = names(item, "editor");
+
+    let (year, month) = issued(item);
+
+    // --- Venue ------------------------------------------------------------
+    let container = title_text(item, "container-title");
+    let publisher = text(item, "publisher");
+
+    let mut venue = Venue {
+        series: title_text(item, "collection-title"),
+        volume: text(item, "volume"),
+        issue: text(item, "issue").or_else(|| text(item, "number")),
+        pages: text(item, "page").map(|p| p.replace('-', "--").replace("----", "--")),
+        address: text(item, "publisher-place"),
+        entry_type: genre,
+        ..Venue::default()
+    };
+
+    venue.set_container(kind, container);
+    venue.set_publisher(kind, publisher);
+
+    // --- Identifiers ------------------------------------------------------
+    let mut identifiers = Identifiers {
+        doi: text(item, "DOI"),
+        isbn: text(item, "ISBN"),
+        url: text(item, "URL"),
+        pmid: text(item, "PMID"),
+        pmcid: text(item, "PMCID"),
+        ..Identifiers::default()
+    };
+
+    if text(item, "archive").is_some_and(|a| a.eq_ignore_ascii_case("arxiv")) {
+        identifiers.arxiv = text(item, "archive_location");
+    }
+
+    // --- Extra ------------------------------------------------------------
+    let mut extra = BTreeMap::new();
+
+    for (csl, field) in EXTRA_VARIABLES {
+        if let Some(v) = text(item, csl) {
+            extra.insert(field.to_string(), v);
+        }
+    }
+
+    if let Some(name) = month.and_then(month_name) {
+        extra.insert("month".to_string(), name.to_string());
+    }
+
+    let mut reference = Reference {
+        id: String::new(),
+        sid: make_sid(),
+        kind,
+        title,
+        authors,
+        editors,
+        year,
+        identifiers,
+        venue: Some(venue),
+        tags: Vec::new(),
+        notes: None,
+        attachments: Vec::new(),
+        extra,
+        aliases: Vec::new(),
+    };
+
+    reference.id = text(item, "id")
+        .and_then(|id| imported_key(&id))
+        .unwrap_or_else(|| make_key(&reference));
+
+    Some(reference)
+}
+
+fn kind_from_csl(csl_type: &str, genre: Option<&str>) -> RefKind {
+    match csl_type {
+        "article-journal" | "article" | "article-magazine" | "article-newspaper" => {
+            RefKind::Article
+        }
+        "paper-conference" => RefKind::InProceedings,
+        "chapter" => RefKind::InCollection,
+        "book" => RefKind::Book,
+        "thesis" => {
+            let masters = genre.is_some_and(|g| g.to_lowercase().contains("master"));
+            if masters { RefKind::MastersThesis } else { RefKind::PhdThesis }
+        }
+        "report" => RefKind::TechReport,
+        "manuscript" => RefKind::Unpublished,
+        "pamphlet" => RefKind::Booklet,
+        "webpage" | "post" | "post-weblog" => RefKind::Online,
+        "dataset" => RefKind::Dataset,
+        "software" => RefKind::Software,
+        "patent" => RefKind::Patent,
+        "standard" => RefKind::Manual,
+        _ => RefKind::Misc,
+    }
+}
+
+/// String or number variable as text.
+fn text(item: &Value, key: &str) -> Option<String> {
+    match item.get(key)? {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Title variable with `nocase` spans turned into BibTeX brace groups.
+fn title_text(item: &Value, key: &str) -> Option<String> {
+    text(item, key).map(|t| nocase_to_case_braces(&t))
+}
+
+/// CSL name objects → structured names.
+fn names(item: &Value, key: &str) -> Vec<Name> {
+    let Some(Value::Array(list)) = item.get(key) else {
+        return Vec::new();
+    };
+
+    list.iter()
+        .filter_map(|n| {
+            if let Some(literal) = text(n, "literal") {
+                return Some(Name::literal(&literal));
+            }
+
+            Some(Name {
+                family: text(n, "family")?,
+                given: text(n, "given"),
+                von: text(n, "non-dropping-particle")
+                    .or_else(|| text(n, "dropping-particle")),
+                jr: text(n, "suffix"),
+                literal: false,
+            })
+        })
+        .collect()
+}
+
+/// `issued` → (year, month), from `date-parts` or a `raw` date string.
+fn issued(item: &Value) -> (Option<u16>, Option<u8>) {
+    let Some(issued) = item.get("issued") else {
+        return (None, None);
+    };
+
+    if let Some(parts) = issued
+        .get("date-parts")
+        .and_then(|d| d.get(0))
+        .and_then(Value::as_array)
+    {
+        let part = |i: usize| {
+            parts.get(i).and_then(|p| match p {
+                Value::Number(n) => n.as_u64(),
+                Value::String(s) => s.parse().ok(),
+                _ => None,
+            })
+        };
+
+        return (
+            part(0).and_then(|y| u16::try_from(y).ok()),
+            part(1).and_then(|m| u8::try_from(m).ok()),
+        );
+    }
+
+    let raw = text(issued, "raw").unwrap_or_default();
+    let mut it = raw.split(['-', '/', ' ']);
+
+    (
+        it.next().and_then(|y| y.parse().ok()),
+        it.next().and_then(|m| m.parse().ok()),
+    )
+}
+
+// ============================================================
+// EXPORT
+// ============================================================
+
+pub fn render_csl_json(refs: &[Reference]) -> String {
+    let items: Vec<Value> = refs.iter().map(reference_to_item).collect();
+
+    serde_json::to_string_pretty(&items).expect("❌ Failed to serialize CSL-JSON")
+}
+
+fn reference_to_item(r: &Reference) -> Value {
+    let mut item = Map::new();
+
+    let mut set = |key: &str, value: Option<&str>| {
+        if let Some(v) = value {
+            item.insert(key.to_string(), Value::String(v.to_string()));
+        }
+    };
+
+    set("id", Some(&r.id));
+    set("type", Some(csl_type(r.kind)));
+    set("title", Some(&case_braces_to_nocase(&r.title)));
+
+    // --- Venue ------------------------------------------------------------
+    if let Some(v) = &r.venue {
+        set(
+            "container-title",
+            v.journal
+                .as_deref()
+                .or(v.booktitle.as_deref())
+                .map(case_braces_to_nocase)
+                .as_deref(),
+        );
+        set(
+            "publisher",
+            v.publisher
+                .as_deref()
+                .or(v.school.as_deref())
+                .or(v.institution.as_deref()),
+        );
+        set("publisher-place", v.address.as_deref());
+        set("collection-title", v.series.as_deref().map(case_braces_to_nocase).as_deref());
+        set("volume", v.volume.as_deref());
+
+        match r.kind {
+            RefKind::TechReport | RefKind::Patent => set("number", v.issue.as_deref()),
+            _ => set("issue", v.issue.as_deref()),
+        }
+
+        set("page", v.pages.as_deref().map(|p| p.replace("--", "-")).as_deref());
+
+        let genre = v.entry_type.as_deref().or(match r.kind {
+            RefKind::PhdThesis => Some("PhD thesis"),
+            RefKind::MastersThesis => Some("Master's thesis"),
+            _ => None,
+        });
+        set("genre", genre);
+    }
+
+    // --- Identifiers ------------------------------------------------------
+    set("DOI", r.identifiers.doi.as_deref());
+    set("ISBN", r.identifiers.isbn.as_deref());
+    set("URL", r.identifiers.url.as_deref());
+    set("PMID", r.identifiers.pmid.as_deref());
+    set("PMCID", r.identifiers.pmcid.as_deref());
+
+    if r.identifiers.arxiv.is_some() {
+        set("archive", Some("arXiv"));
+        set("archive_location", r.identifiers.arxiv.as_deref());
+    }
+
+    // --- Extra ------------------------------------------------------------
+    for (csl, field) in EXTRA_VARIABLES {
+        set(csl, r.extra.get(field).map(String::as_str));
+    }
+
+    // --- Names and date ---------------------------------------------------
+    if !r.authors.is_empty() {
+        item.insert("author".into(), Value::Array(r.authors.iter().map(csl_name).collect()));
+    }
+
+    if !r.editors.is_empty() {
+        item.insert("editor".into(), Value::Array(r.editors.iter().map(csl_name).collect()));
+    }
+
+    if let Some(y) = r.year {
+        let parts = match r.extra.get("month").and_then(|m| month_number(m)) {
+            Some(m) => json!([[y, m]]),
+            None => json!([[y]]),
+        };
+        item.insert("issued".into(), json!({ "date-parts": parts }));
+    }
+
+    Value::Object(item)
+}
+
+fn csl_type(kind: RefKind) -> &'static str {
+    match kind {
+        RefKind::Article => "article-journal",
+        RefKind::InProceedings => "paper-conference",
+        RefKind::InCollection | RefKind::InBook => "chapter",
+        RefKind::Book | RefKind::Proceedings => "book",
+        RefKind::PhdThesis | RefKind::MastersThesis => "thesis",
+        RefKind::TechReport => "report",
+        RefKind::Manual => "standard",
+        RefKind::Unpublished => "manuscript",
+        RefKind::Booklet => "pamphlet",
+        RefKind::Online => "webpage",
+        RefKind::Dataset => "dataset",
+        RefKind::Software => "software",
+        RefKind::Patent => "patent",
+        RefKind::Misc => "document",
+    }
+}
+
+/// Structured name → CSL name object.
+fn csl_name(name: &Name) -> Value {
+    if name.literal {
+        return json!({ "literal": name.family });
+    }
+
+    let mut out = Map::new();
+    out.insert("family".into(), Value::String(name.family.clone()));
+
+    let optional = [
+        ("given", &name.given),
+        ("non-dropping-particle", &name.von),
+        ("suffix", &name.jr),
+    ];
+    for (key, value) in optional {
+        if let Some(v) = value {
+            out.insert(key.into(), Value::String(v.clone()));
+        }
+    }
+
+    Value::Object(out)
+}
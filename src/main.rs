@@ -1,12 +1,18 @@
 use clap::{Parser, Subcommand};
 
 mod state;
+mod backup;
+mod config;
 mod commands;
 mod project;
 mod project_store;
 mod reference;
 mod reference_store;
 mod bibtex;
+mod csl;
+mod ris;
+mod nbib;
+mod migrate;
 mod utils;
 mod search;
 
@@ -91,11 +97,48 @@ enum Commands {
     /// Analyze LaTeX project and mark uncited references
     Purge {
         /// Path to LaTeX project root
-        path: String,
+        #[arg(required_unless_present = "restore")]
+        path: Option<String>,
 
-        /// Apply changes (default is dry run)
+        /// Rewrite the .bib files in place, keeping a backup (default writes *_purged.bib copies)
         #[arg(long)]
         force: bool,
+
+        /// Where cited keys come from: build artifacts (.aux/.bcf), .tex sources, or auto
+        #[arg(long = "source", value_parser = ["auto", "build", "tex"], default_value = "auto")]
+        source: String,
+
+        /// Also unpin uncited references from a library (default: active library)
+        #[arg(long = "unpin", value_name = "LIBRARY", num_args = 0..=1, requires = "force")]
+        unpin: Option<Option<String>>,
+
+        /// Undo a purge --force from its backup (name, prefix, path or `latest`)
+        #[arg(long = "restore", value_name = "BACKUP", conflicts_with_all = ["force", "unpin"])]
+        restore: Option<String>,
+    },
+
+    /// Report citations missing from the library, the registry and the .bib files
+    CheckTex {
+        /// Path to LaTeX project root
+        path: String,
+
+        /// Library to check against (default: active library)
+        #[arg(long = "lib")]
+        library: Option<String>,
+
+        /// Pin cited references that are in the registry but not the library
+        #[arg(long)]
+        pin: bool,
+    },
+
+    /// List the keys a LaTeX project cites
+    Cited {
+        /// Path to LaTeX project root
+        path: String,
+
+        /// Where cited keys come from: build artifacts (.aux/.bcf), .tex sources, or auto
+        #[arg(long = "source", value_parser = ["auto", "build", "tex"], default_value = "auto")]
+        source: String,
     },
 
     Search {
@@ -112,14 +155,119 @@ enum Commands {
         #[arg(long = "rename")]
         rename: bool,
 
+        /// Default .bib path for `eln printed` ("" restores the default)
+        #[arg(long = "output", value_name = "PATH", conflicts_with_all = ["delete", "rename"])]
+        output: Option<String>,
+
         library_id: Option<String>,
     },
 
+    /// Check references against BibTeX field rules (non-zero exit on errors)
+    Lint {
+        /// Library to check (repeatable; defaults to the active library)
+        #[arg(long = "lib")]
+        libs: Vec<String>,
+
+        /// Check every library
+        #[arg(long = "all", conflicts_with = "libs")]
+        all: bool,
+
+        #[arg(long = "format", value_parser = ["text", "json"], default_value = "text")]
+        format: String,
+    },
+
+    /// Migrate an EndNote or Zotero library export (offline)
+    Import {
+        #[arg(long = "from", value_parser = ["endnote-xml", "zotero-rdf"])]
+        from: String,
+
+        /// Exported library file (.xml / .rdf)
+        path: String,
+    },
+
+    /// Export references to another bibliography format
+    Export {
+        #[arg(long = "format", value_parser = ["csl-json", "ris"], default_value = "csl-json")]
+        format: String,
+
+        #[arg(long = "all")]
+        all: bool,
+
+        projects: Vec<String>,
+    },
+
+    /// List references that look like duplicates of each other
+    Dedupe,
+
+    /// Merge <drop> into <keep>; <drop>'s ID stays as an alias
+    Merge {
+        keep: String,
+        drop: String,
+    },
+
+    /// Rename a citation key in the registry and in LaTeX sources
+    RenameKey {
+        old: String,
+        new: String,
+
+        /// LaTeX project (directory or .tex file) whose citations to rewrite
+        #[arg(long = "tex")]
+        tex: Option<String>,
+
+        /// Show the diff without changing anything
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
+
+    /// Regenerate reference IDs from `citekey.format` in .elaine/config.yaml
+    Rekey {
+        #[arg(long = "all")]
+        all: bool,
+
+        /// Show the renames without applying them
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+
+        projects: Vec<String>,
+    },
+
     /// Print BibTeX for one or more projects (union)
     Printed {
         #[arg(long = "all")]
         all: bool,
 
+        /// Output field conventions
+        #[arg(long = "dialect", value_parser = ["bibtex", "biblatex"], default_value = "bibtex")]
+        dialect: String,
+
+        /// Non-ASCII handling (default: latex for bibtex, utf8 for biblatex)
+        #[arg(long = "escape", value_parser = ["latex", "utf8"])]
+        escape: Option<String>,
+
+        /// Emit former IDs too (`ids` under biblatex, copies under bibtex)
+        #[arg(long = "with-aliases")]
+        with_aliases: bool,
+
+        /// Only the references cited in this LaTeX project, from the whole registry
+        #[arg(long = "for-tex", value_name = "DIR", conflicts_with_all = ["all", "projects"])]
+        for_tex: Option<String>,
+
+        /// Keep running and rebuild the .bib whenever the registry (or the .tex) changes
+        #[arg(long = "watch", conflicts_with = "stdout")]
+        watch: bool,
+
+        /// Output .bib path (default: the library's stored path, else <libs>_references.bib)
+        #[arg(short = 'o', long = "output", value_name = "PATH", conflicts_with = "stdout")]
+        output: Option<String>,
+
+        /// Print the bibliography to stdout only; no file is written
+        #[arg(long = "stdout", conflicts_with = "quiet")]
+        stdout: bool,
+
+        /// Write the file without echoing the bibliography or a summary
+        #[arg(short = 'q', long = "quiet")]
+        quiet: bool,
+
         projects: Vec<String>,
     },
 }
@@ -152,18 +300,66 @@ fn main() {
         Commands::Status { verbose, sort } =>
             commands::status::run_status(verbose, sort),
 
-        Commands::Purge { path, force } =>
-            commands::purge::run_purge(path, force),
+        Commands::Purge { path, force, source, unpin, restore } =>
+            commands::purge::run_purge(path, force, source, unpin, restore),
 
-        Commands::Search { ref_selector } =>
-            commands::search::run_search(ref_selector),
+        Commands::CheckTex { path, library, pin } =>
+            commands::check_tex::run_check_tex(path, library, pin),
 
-        Commands::Lib { library_id, delete, rename } =>
-            commands::pro::run_pro(library_id, delete, rename),
+        Commands::Cited { path, source } =>
+            commands::cited::run_cited(path, source),
 
+        Commands::Search { ref_selector } =>
+            commands::search::run_search(ref_selector),
 
-        Commands::Printed { all, projects } => {
-            commands::printed::run_printed(all, projects)
-        }
+        Commands::Lib { library_id, delete, rename, output } =>
+            commands::pro::run_pro(library_id, delete, rename, output),
+
+
+        Commands::Lint { libs, all, format } =>
+            commands::lint::run_lint(all, libs, format),
+
+        Commands::Import { from, path } =>
+            commands::import::run_import(from, path),
+
+        Commands::Export { format, all, projects } =>
+            commands::export::run_export(format, all, projects),
+
+        Commands::Dedupe => commands::dedupe::run_dedupe(),
+
+        Commands::Merge { keep, drop } =>
+            commands::merge::run_merge(keep, drop),
+
+        Commands::RenameKey { old, new, tex, dry_run } =>
+            commands::rename_key::run_rename_key(old, new, tex, dry_run),
+
+        Commands::Rekey { all, dry_run, projects } =>
+            commands::rekey::run_rekey(all, projects, dry_run),
+
+        Commands::Printed {
+            all,
+            projects,
+            dialect,
+            escape,
+            with_aliases,
+            for_tex,
+            watch,
+            output,
+            stdout,
+            quiet,
+        } => commands::printed::run_printed(
+            all,
+            projects,
+            dialect,
+            escape,
+            with_aliases,
+            for_tex,
+            commands::printed::OutputTarget {
+                path: output,
+                stdout,
+                quiet,
+                watch,
+            },
+        ),
     }
 }
\ No newline at end of file
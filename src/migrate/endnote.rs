@@ -0,0 +1,238 @@
+//! EndNote XML (`File → Export → XML`).
+//!
+//! Each `<record>` becomes a `Reference`. EndNote does not export its
+//! groups, so the source library (`<database>`, e.g. `Thesis.enl`)
+//! becomes the Elaine library. PDFs stored as `internal-pdf://` links are
+//! looked up in the `<Library>.Data/PDF/` folder next to the export.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use roxmltree::{Document, Node};
+
+use super::{deep_text, file_url_to_path, non_empty, resolve_attachment, Migration};
+use crate::bibtex::month_name;
+use crate::bibtex::names::{parse_name, Name};
+use crate::reference::{Identifiers, RefKind, Reference, Venue};
+use crate::utils::id::make_sid;
+use crate::utils::normalize::{normalize_pages, parse_date, strip_doi_prefix};
+
+pub fn parse_endnote_xml(input: &str, base: &Path) -> Result<Migration, String> {
+    let doc = Document::parse(input).map_err(|e| e.to_string())?;
+    let mut migration = Migration::default();
+    let mut taken = HashSet::new();
+
+    for (i, record) in doc
+        .descendants()
+        .filter(|n| n.has_tag_name("record"))
+        .enumerate()
+    {
+        let label = child(record, "rec-number")
+            .map(|n| format!("record #{}", n))
+            .unwrap_or_else(|| format!("record {}", i + 1));
+
+        let Some(mut r) = record_to_reference(record) else {
+            migration.skip(label, "missing title");
+            continue;
+        };
+
+        migration.assign_id(&mut r, &mut taken);
+
+        // --- Attachments -----------------------------------------------------
+        let database = child(record, "database");
+
+        for url in path_texts(record, &["urls", "pdf-urls", "url"]) {
+            match attachment_path(&url, database.as_deref(), base) {
+                Some(p) => r.attachments.push(p),
+                None => migration.skip(
+                    r.title.clone(),
+                    format!("attachment not found: {}", url),
+                ),
+            }
+        }
+
+        if let Some(db) = database {
+            let name = db.trim_end_matches(".enl").to_string();
+            migration.add_to_collection(&name, &r.id);
+        }
+
+        migration.refs.push(r);
+    }
+
+    Ok(migration)
+}
+
+fn record_to_reference(record: Node) -> Option<Reference> {
+    let title = path_text(record, &["titles", "title"])?;
+
+    let ref_type = record
+        .children()
+        .find(|n| n.has_tag_name("ref-type"))
+        .and_then(|n| n.attribute("name"))
+        .unwrap_or("Generic");
+
+    let work_type = child(record, "work-type");
+    let kind = kind_from_endnote(ref_type, work_type.as_deref());
+
+    let authors = endnote_names(path_texts(record, &["contributors", "authors", "author"]));
+    let editors = endnote_names(path_texts(record, &["contributors", "secondary-authors", "author"]));
+
+    let year = path_text(record, &["dates", "year"]).and_then(|y| y.get(..4)?.parse().ok());
+    let month = path_text(record, &["dates", "pub-dates", "date"]).and_then(|d| parse_date(&d).1);
+
+    // --- Venue ------------------------------------------------------------
+    let container = path_text(record, &["titles", "secondary-title"])
+        .or_else(|| path_text(record, &["periodical", "full-title"]));
+    let publisher = child(record, "publisher");
+
+    let mut venue = Venue {
+        series: path_text(record, &["titles", "tertiary-title"]),
+        volume: child(record, "volume"),
+        issue: child(record, "number"),
+        pages: child(record, "pages").map(|p| normalize_pages(&p)),
+        address: child(record, "pub-location"),
+        entry_type: work_type,
+        ..Venue::default()
+    };
+
+    venue.set_container(kind, container);
+    venue.set_publisher(kind, publisher);
+
+    // --- Identifiers ------------------------------------------------------
+    let mut extra = BTreeMap::new();
+
+    // <isbn> holds the ISSN for periodicals
+    let isbn = match (child(record, "isbn"), kind) {
+        (Some(sn), RefKind::Article) => {
+            extra.insert("issn".to_string(), sn);
+            None
+        }
+        (sn, _) => sn,
+    };
+
+    let identifiers = Identifiers {
+        doi: child(record, "electronic-resource-num").map(|d| strip_doi_prefix(&d)),
+        isbn,
+        url: path_text(record, &["urls", "related-urls", "url"])
+            .or_else(|| path_text(record, &["urls", "web-urls", "url"])),
+        ..Identifiers::default()
+    };
+
+    // --- Extra ------------------------------------------------------------
+    if let Some(ab) = child(record, "abstract") {
+        extra.insert("abstract".to_string(), ab);
+    }
+
+    let keywords = path_texts(record, &["keywords", "keyword"]);
+    if !keywords.is_empty() {
+        extra.insert("keywords".to_string(), keywords.join(", "));
+    }
+
+    if let Some(edition) = child(record, "edition") {
+        extra.insert("edition".to_string(), edition);
+    }
+
+    if let Some(abbr) = path_text(record, &["alt-periodical", "abbr-1"])
+        .or_else(|| path_text(record, &["periodical", "abbr-1"]))
+    {
+        extra.insert("shortjournal".to_string(), abbr);
+    }
+
+    if let Some(name) = month.and_then(month_name) {
+        extra.insert("month".to_string(), name.to_string());
+    }
+
+    Some(Reference {
+        id: String::new(),
+        sid: make_sid(),
+        kind,
+        title,
+        authors,
+        editors,
+        year,
+        identifiers,
+        venue: Some(venue),
+        tags: Vec::new(),
+        notes: child(record, "research-notes").or_else(|| child(record, "notes")),
+        attachments: Vec::new(),
+        extra,
+        aliases: Vec::new(),
+    })
+}
+
+/// EndNote marks corporate authors with a trailing comma
+/// (`World Health Organization,`).
+fn endnote_names(values: Vec<String>) -> Vec<Name> {
+    values
+        .iter()
+        .map(|v| match v.strip_suffix(',') {
+            Some(corporate) => Name::literal(corporate),
+            None => parse_name(v),
+        })
+        .collect()
+}
+
+fn kind_from_endnote(ref_type: &str, work_type: Option<&str>) -> RefKind {
+    match ref_type {
+        "Journal Article" | "Electronic Article" | "Magazine Article" | "Newspaper Article" => {
+            RefKind::Article
+        }
+        "Book" | "Edited Book" | "Electronic Book" => RefKind::Book,
+        "Book Section" | "Electronic Book Section" => RefKind::InCollection,
+        "Conference Paper" => RefKind::InProceedings,
+        "Conference Proceedings" => RefKind::Proceedings,
+        "Thesis" => {
+            let masters = work_type.is_some_and(|w| w.to_lowercase().contains("master"));
+            if masters { RefKind::MastersThesis } else { RefKind::PhdThesis }
+        }
+        "Report" | "Government Document" => RefKind::TechReport,
+        "Manuscript" | "Unpublished Work" => RefKind::Unpublished,
+        "Web Page" | "Blog" => RefKind::Online,
+        "Dataset" => RefKind::Dataset,
+        "Computer Program" => RefKind::Software,
+        "Patent" => RefKind::Patent,
+        "Standard" => RefKind::Manual,
+        "Pamphlet" => RefKind::Booklet,
+        _ => RefKind::Misc,
+    }
+}
+
+/// `internal-pdf://0123456789/paper.pdf` → `<Library>.Data/PDF/0123456789/paper.pdf`
+fn attachment_path(url: &str, database: Option<&str>, base: &Path) -> Option<String> {
+    if let Some(rest) = url.strip_prefix("internal-pdf://") {
+        let db = database?.trim_end_matches(".enl");
+        let rel = urlencoding::decode(rest).ok()?;
+        return resolve_attachment(base, &format!("{}.Data/PDF/{}", db, rel));
+    }
+
+    let path = file_url_to_path(url).unwrap_or_else(|| url.to_string());
+    resolve_attachment(base, &path)
+}
+
+// --- Tree helpers ----------------------------------------------------------
+
+fn child(node: Node, name: &str) -> Option<String> {
+    path_text(node, &[name])
+}
+
+/// Text of the first node at `path` (direct children at each step).
+fn path_text(node: Node, path: &[&str]) -> Option<String> {
+    path_texts(node, path).into_iter().next()
+}
+
+/// Text of every node at `path`, skipping empty values.
+fn path_texts(node: Node, path: &[&str]) -> Vec<String> {
+    let mut current = vec![node];
+
+    for name in path {
+        current = current
+            .iter()
+            .flat_map(|n| n.children().filter(|c| c.has_tag_name(*name)))
+            .collect();
+    }
+
+    current
+        .into_iter()
+        .filter_map(|n| non_empty(deep_text(n)))
+        .collect()
+}
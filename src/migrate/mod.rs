@@ -0,0 +1,89 @@
+//! Offline importers for other reference managers' library exports.
+//!
+//! Unlike `eln add`, a migration also carries library structure
+//! (collections / groups) and attachment links.
+
+pub mod endnote;
+pub mod zotero;
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use roxmltree::Node;
+
+use crate::reference::Reference;
+use crate::utils::citekey::make_key;
+use crate::utils::id::disambiguate;
+
+/// Everything recovered from one export file.
+#[derive(Default)]
+pub struct Migration {
+    pub refs: Vec<Reference>,
+    /// Library name → IDs of its member references
+    pub collections: BTreeMap<String, Vec<String>>,
+    pub skipped: Vec<Skipped>,
+}
+
+/// An item (or part of one) that could not be imported.
+pub struct Skipped {
+    /// Human-readable label: title, record number, or file name
+    pub item: String,
+    pub reason: String,
+}
+
+impl Migration {
+    fn skip(&mut self, item: impl Into<String>, reason: impl Into<String>) {
+        self.skipped.push(Skipped {
+            item: item.into(),
+            reason: reason.into(),
+        });
+    }
+
+    /// Generated keys collide easily across thousands of items;
+    /// later duplicates get `a`, `b`, ... suffixes.
+    fn assign_id(&self, r: &mut Reference, taken: &mut HashSet<String>) {
+        let base = make_key(r);
+        r.id = disambiguate(&base, |id| taken.contains(id));
+        taken.insert(r.id.clone());
+    }
+
+    fn add_to_collection(&mut self, name: &str, ref_id: &str) {
+        let members = self.collections.entry(name.to_string()).or_default();
+        if !members.iter().any(|m| m == ref_id) {
+            members.push(ref_id.to_string());
+        }
+    }
+}
+
+/// Concatenated text of a node and its descendants, whitespace-collapsed.
+/// EndNote wraps most values in `<style>` runs.
+fn deep_text(node: Node) -> String {
+    let raw: String = node
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect();
+
+    raw.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn non_empty(s: String) -> Option<String> {
+    if s.is_empty() { None } else { Some(s) }
+}
+
+/// Resolve an attachment path against the export's directory and
+/// return it canonicalised, like `eln attach` stores it.
+fn resolve_attachment(base: &Path, path: &str) -> Option<String> {
+    let p = Path::new(path);
+    let p = if p.is_absolute() { p.to_path_buf() } else { base.join(p) };
+
+    p.canonicalize()
+        .ok()
+        .map(|abs| abs.to_string_lossy().to_string())
+}
+
+/// `file:///home/a/b%20c.pdf` → `/home/a/b c.pdf`
+fn file_url_to_path(url: &str) -> Option<String> {
+    let path = url.strip_prefix("file://")?;
+    urlencoding::decode(path).ok().map(|p| p.into_owned())
+}
@@ -0,0 +1,346 @@
+//! Zotero RDF (`File → Export Library… → Zotero RDF`).
+//!
+//! Items are the top-level RDF resources carrying a `z:itemType`.
+//! `z:Collection`s become Elaine libraries, and `z:Attachment`s linked
+//! from an item (`link:link`) become `Reference.attachments`, resolved
+//! against the `files/` folder Zotero writes next to the export.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use regex::Regex;
+use roxmltree::{Document, Node};
+
+use super::{deep_text, file_url_to_path, non_empty, resolve_attachment, Migration};
+use crate::bibtex::month_name;
+use crate::bibtex::names::{parse_name, Name};
+use crate::reference::{Identifiers, RefKind, Reference, Venue};
+use crate::utils::id::make_sid;
+use crate::utils::normalize::{normalize_pages, parse_date};
+
+const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const Z: &str = "http://www.zotero.org/namespaces/export#";
+const DC: &str = "http://purl.org/dc/elements/1.1/";
+const DCTERMS: &str = "http://purl.org/dc/terms/";
+const BIB: &str = "http://purl.org/net/biblio#";
+const FOAF: &str = "http://xmlns.com/foaf/0.1/";
+const PRISM: &str = "http://prismstandard.org/namespaces/1.2/basic/";
+const LINK: &str = "http://purl.org/rss/1.0/modules/link/";
+const VCARD: &str = "http://nwalsh.com/rdf/vCard#";
+
+pub fn parse_zotero_rdf(input: &str, base: &Path) -> Result<Migration, String> {
+    let doc = Document::parse(input).map_err(|e| e.to_string())?;
+    let root = doc.root_element();
+    let mut migration = Migration::default();
+    let mut taken = HashSet::new();
+
+    // Index every top-level resource by its rdf:about
+    let resources: HashMap<&str, Node> = root
+        .children()
+        .filter(Node::is_element)
+        .filter_map(|n| Some((n.attribute((RDF, "about"))?, n)))
+        .collect();
+
+    // about → Elaine reference ID
+    let mut ids: HashMap<&str, String> = HashMap::new();
+    let mut linked_attachments: HashSet<&str> = HashSet::new();
+    let mut linked_notes: HashSet<&str> = HashSet::new();
+
+    for node in root.children().filter(Node::is_element) {
+        let Some(item_type) = text(node, Z, "itemType") else {
+            continue;
+        };
+
+        let label = text(node, DC, "title").unwrap_or_else(|| item_type.clone());
+
+        if item_type == "attachment" || item_type == "note" {
+            continue;
+        }
+
+        let Some(mut r) = item_to_reference(node, &item_type) else {
+            migration.skip(label, format!("{} without a title", item_type));
+            continue;
+        };
+
+        migration.assign_id(&mut r, &mut taken);
+
+        // --- Attachments -----------------------------------------------------
+        for link in children(node, LINK, "link") {
+            let Some(target) = link.attribute((RDF, "resource")) else {
+                continue;
+            };
+            linked_attachments.insert(target);
+
+            let Some(attachment) = resources.get(target) else {
+                continue;
+            };
+
+            // Web links have no local file
+            let Some(resource) = attachment_resource(*attachment) else {
+                continue;
+            };
+
+            match attachment_path(&resource, base) {
+                Some(p) => r.attachments.push(p),
+                None => migration.skip(
+                    r.title.clone(),
+                    format!("attachment not found: {}", resource),
+                ),
+            }
+        }
+
+        // --- Child notes -------------------------------------------------------
+        let notes: Vec<String> = children(node, DCTERMS, "isReferencedBy")
+            .into_iter()
+            .filter_map(|n| n.attribute((RDF, "resource")))
+            .filter_map(|target| {
+                linked_notes.insert(target);
+                resources.get(target).and_then(|memo| memo_text(*memo))
+            })
+            .collect();
+
+        if !notes.is_empty() {
+            r.notes = Some(notes.join("\n\n"));
+        }
+
+        if let Some(about) = node.attribute((RDF, "about")) {
+            ids.insert(about, r.id.clone());
+        }
+
+        migration.refs.push(r);
+    }
+
+    // --- Standalone attachments and notes ------------------------------------
+    for node in root.children().filter(Node::is_element) {
+        let about = node.attribute((RDF, "about")).unwrap_or_default();
+
+        if node.has_tag_name((Z, "Attachment")) && !linked_attachments.contains(about) {
+            let label = text(node, DC, "title").unwrap_or_else(|| about.to_string());
+            migration.skip(label, "standalone attachment");
+        }
+
+        if node.has_tag_name((BIB, "Memo")) && !linked_notes.contains(about) {
+            migration.skip(about, "standalone note");
+        }
+    }
+
+    // --- Collections → libraries ----------------------------------------------
+    for collection in root.children().filter(|n| n.has_tag_name((Z, "Collection"))) {
+        let Some(name) = text(collection, DC, "title") else {
+            continue;
+        };
+
+        for part in children(collection, DCTERMS, "hasPart") {
+            // Sub-collections are libraries of their own
+            if let Some(id) = part.attribute((RDF, "resource")).and_then(|r| ids.get(r)) {
+                migration.add_to_collection(&name, id);
+            }
+        }
+    }
+
+    Ok(migration)
+}
+
+fn item_to_reference(node: Node, item_type: &str) -> Option<Reference> {
+    let title = text(node, DC, "title")?;
+    let kind = kind_from_zotero(item_type);
+
+    let authors = people(node, "authors");
+    let editors = people(node, "editors");
+
+    let (year, month) = text(node, DC, "date").map_or((None, None), |d| parse_date(&d));
+
+    // --- Venue ------------------------------------------------------------
+    // Journal / book / proceedings details live on the isPartOf resource
+    let container = children(node, DCTERMS, "isPartOf")
+        .into_iter()
+        .find_map(|p| p.children().find(Node::is_element));
+
+    let from_container = |ns: &str, name: &str| {
+        container.and_then(|c| text(c, ns, name)).or_else(|| text(node, ns, name))
+    };
+
+    let (publisher, address) = match children(node, DC, "publisher").first() {
+        Some(p) => (
+            descendant_text(*p, FOAF, "name"),
+            descendant_text(*p, VCARD, "locality"),
+        ),
+        None => (None, None),
+    };
+
+    let container_title = container.and_then(|c| text(c, DC, "title"));
+
+    let mut venue = Venue {
+        series: container
+            .and_then(|c| children(c, DCTERMS, "isPartOf").first().copied())
+            .and_then(|s| descendant_text(s, DC, "title")),
+        volume: from_container(PRISM, "volume"),
+        issue: from_container(PRISM, "number"),
+        pages: text(node, BIB, "pages").map(|p| normalize_pages(&p)),
+        address,
+        entry_type: text(node, Z, "type"),
+        ..Venue::default()
+    };
+
+    venue.set_container(kind, container_title);
+    venue.set_publisher(kind, publisher);
+
+    // --- Identifiers ------------------------------------------------------
+    let mut identifiers = Identifiers::default();
+    let mut extra = BTreeMap::new();
+
+    let mut id_values: Vec<String> = children(node, DC, "identifier")
+        .into_iter()
+        .map(|i| deep_text(i))
+        .collect();
+    if let Some(c) = container {
+        id_values.extend(children(c, DC, "identifier").into_iter().map(|i| deep_text(i)));
+    }
+
+    for value in id_values {
+        if let Some(doi) = value.strip_prefix("DOI ") {
+            identifiers.doi = Some(doi.trim().to_string());
+        } else if let Some(isbn) = value.strip_prefix("ISBN ") {
+            identifiers.isbn = Some(isbn.trim().to_string());
+        } else if let Some(issn) = value.strip_prefix("ISSN ") {
+            extra.insert("issn".to_string(), issn.trim().to_string());
+        } else if value.starts_with("http://") || value.starts_with("https://") {
+            identifiers.url = Some(value);
+        }
+    }
+
+    // Zotero keeps DOIs of non-article types in the Extra field
+    let description = text(node, DC, "description");
+    if identifiers.doi.is_none()
+        && let Some(doi) = description
+            .as_deref()
+            .and_then(|d| d.lines().find_map(|l| l.strip_prefix("DOI: ")))
+    {
+        identifiers.doi = Some(doi.trim().to_string());
+    }
+
+    // --- Extra ------------------------------------------------------------
+    if let Some(ab) = text(node, DCTERMS, "abstract") {
+        extra.insert("abstract".to_string(), ab);
+    }
+
+    let keywords: Vec<String> = children(node, DC, "subject")
+        .into_iter()
+        .filter_map(|s| non_empty(deep_text(s)))
+        .collect();
+    if !keywords.is_empty() {
+        extra.insert("keywords".to_string(), keywords.join(", "));
+    }
+
+    if let Some(lang) = text(node, Z, "language") {
+        extra.insert("language".to_string(), lang);
+    }
+
+    if let Some(abbr) = from_container(DCTERMS, "alternative") {
+        extra.insert("shortjournal".to_string(), abbr);
+    }
+
+    if let Some(name) = month.and_then(month_name) {
+        extra.insert("month".to_string(), name.to_string());
+    }
+
+    Some(Reference {
+        id: String::new(),
+        sid: make_sid(),
+        kind,
+        title,
+        authors,
+        editors,
+        year,
+        identifiers,
+        venue: Some(venue),
+        tags: Vec::new(),
+        notes: None,
+        attachments: Vec::new(),
+        extra,
+        aliases: Vec::new(),
+    })
+}
+
+fn kind_from_zotero(item_type: &str) -> RefKind {
+    match item_type {
+        "journalArticle" | "magazineArticle" | "newspaperArticle" => RefKind::Article,
+        "book" => RefKind::Book,
+        "bookSection" | "encyclopediaArticle" | "dictionaryEntry" => RefKind::InCollection,
+        "conferencePaper" => RefKind::InProceedings,
+        "thesis" => RefKind::PhdThesis,
+        "report" => RefKind::TechReport,
+        "manuscript" => RefKind::Unpublished,
+        "webpage" | "blogPost" | "forumPost" => RefKind::Online,
+        "dataset" => RefKind::Dataset,
+        "computerProgram" => RefKind::Software,
+        "patent" => RefKind::Patent,
+        "standard" => RefKind::Manual,
+        _ => RefKind::Misc,
+    }
+}
+
+/// `bib:authors` / `bib:editors` → structured names
+fn people(node: Node, role: &str) -> Vec<Name> {
+    let Some(seq) = children(node, BIB, role).first().copied() else {
+        return Vec::new();
+    };
+
+    seq.descendants()
+        .filter(|n| n.has_tag_name((FOAF, "Person")) || n.has_tag_name((FOAF, "Organization")))
+        .filter_map(|p| {
+            if let Some(name) = text(p, FOAF, "name") {
+                // Single-field (institutional) names are kept whole
+                return Some(Name::literal(&name));
+            }
+            let surname = text(p, FOAF, "surname")?;
+            let given = text(p, FOAF, "givenName").or_else(|| text(p, FOAF, "givenname"));
+
+            // Particles and suffixes are part of Zotero's surname field
+            Some(match given {
+                Some(given) => parse_name(&format!("{}, {}", surname, given)),
+                None => parse_name(&surname),
+            })
+        })
+        .collect()
+}
+
+/// Local file for an attachment resource, if it exists on disk.
+fn attachment_path(resource: &str, base: &Path) -> Option<String> {
+    let path = file_url_to_path(resource).unwrap_or_else(|| resource.to_string());
+    resolve_attachment(base, &path)
+}
+
+/// Plain text of a `bib:Memo` (Zotero stores notes as HTML).
+fn memo_text(memo: Node) -> Option<String> {
+    let html = text(memo, RDF, "value")?;
+    let plain = Regex::new(r"<[^>]+>").unwrap().replace_all(&html, " ");
+    non_empty(plain.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn attachment_resource(attachment: Node) -> Option<String> {
+    attachment
+        .children()
+        .find(|c| c.has_tag_name((RDF, "resource")))
+        .and_then(|c| c.attribute((RDF, "resource")))
+        .map(str::to_string)
+}
+
+// --- Tree helpers ----------------------------------------------------------
+
+fn children<'a, 'i>(node: Node<'a, 'i>, ns: &str, name: &str) -> Vec<Node<'a, 'i>> {
+    node.children().filter(|c| c.has_tag_name((ns, name))).collect()
+}
+
+/// Text of the first direct child `ns:name`.
+fn text(node: Node, ns: &str, name: &str) -> Option<String> {
+    children(node, ns, name)
+        .into_iter()
+        .find_map(|c| non_empty(deep_text(c)))
+}
+
+fn descendant_text(node: Node, ns: &str, name: &str) -> Option<String> {
+    node.descendants()
+        .filter(|c| c.has_tag_name((ns, name)))
+        .find_map(|c| non_empty(deep_text(c)))
+}
@@ -0,0 +1,253 @@
+//! PubMed / MEDLINE (`.nbib`) import.
+//!
+//! Each record is a block of `TAG - value` lines, with the tag
+//! left-aligned in a four-character column. Long values continue on
+//! lines indented by six spaces:
+//!
+//! ```text
+//! PMID- 31452104
+//! TI  - A very long title that continues
+//!       on the next line.
+//! FAU - Smith, John A
+//! ```
+
+use std::collections::BTreeMap;
+
+use colored::*;
+
+use crate::bibtex::month_name;
+use crate::bibtex::names::{parse_name, Name};
+use crate::reference::{Identifiers, RefKind, Reference, Venue};
+use crate::utils::citekey::make_key;
+use crate::utils::id::make_sid;
+use crate::utils::normalize::parse_date;
+
+/// Parse every MEDLINE record in `input`.
+/// Records without a title are skipped with a warning.
+pub fn parse_nbib(input: &str) -> Vec<Reference> {
+    let mut refs = Vec::new();
+    let mut record: Vec<(String, String)> = Vec::new();
+
+    let mut flush = |record: &mut Vec<(String, String)>| {
+        if record.is_empty() {
+            return;
+        }
+        match record_to_reference(record) {
+            Some(r) => refs.push(r),
+            None => eprintln!(
+                "{}",
+                format!(
+                    "⚠️  Skipping MEDLINE record{} (missing title)",
+                    first(record, &["PMID"])
+                        .map(|p| format!(" PMID {}", p))
+                        .unwrap_or_default()
+                )
+                .yellow()
+            ),
+        }
+        record.clear();
+    };
+
+    for line in input.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim_end();
+
+        if line.trim().is_empty() {
+            flush(&mut record);
+            continue;
+        }
+
+        // Continuation lines are indented; the value resumes after them
+        if line.starts_with(' ') {
+            if let Some((_, last)) = record.last_mut() {
+                last.push(' ');
+                last.push_str(line.trim());
+            }
+            continue;
+        }
+
+        let Some((tag, value)) = split_tag(line) else {
+            continue;
+        };
+
+        // Records are normally blank-line separated, but a new PMID
+        // always starts a new one
+        if tag == "PMID" {
+            flush(&mut record);
+        }
+
+        record.push((tag.to_string(), value.to_string()));
+    }
+
+    flush(&mut record);
+    refs
+}
+
+/// `FAU - Smith, John A` → (`FAU`, `Smith, John A`)
+fn split_tag(line: &str) -> Option<(&str, &str)> {
+    let (tag, value) = line.split_once('-')?;
+    let tag = tag.trim_end();
+
+    if tag.is_empty() || tag.len() > 4 || !tag.chars().all(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+
+    Some((tag, value.trim()))
+}
+
+fn all(record: &[(String, String)], tags: &[&str]) -> Vec<String> {
+    record
+        .iter()
+        .filter(|(t, v)| tags.contains(&t.as_str()) && !v.is_empty())
+        .map(|(_, v)| v.clone())
+        .collect()
+}
+
+/// First value for the highest-priority tag present.
+fn first(record: &[(String, String)], tags: &[&str]) -> Option<String> {
+    tags.iter().find_map(|t| all(record, &[*t]).into_iter().next())
+}
+
+fn record_to_reference(record: &[(String, String)]) -> Option<Reference> {
+    let book_title = first(record, &["BTI"]);
+    let title = first(record, &["TI"]).or_else(|| book_title.clone())?;
+    let title = title.trim_end_matches('.').to_string();
+
+    // PubMed Bookshelf records carry BTI; a TI alongside it is a chapter
+    let kind = match (&book_title, first(record, &["TI"])) {
+        (Some(_), Some(_)) => RefKind::InCollection,
+        (Some(_), None) => RefKind::Book,
+        _ => RefKind::Article,
+    };
+
+    // --- Names ------------------------------------------------------------
+    let mut authors: Vec<Name> = all(record, &["FAU"]).iter().map(|a| parse_name(a)).collect();
+    if authors.is_empty() {
+        authors = all(record, &["AU"]).iter().map(|a| parse_name(&expand_initials(a))).collect();
+    }
+    if authors.is_empty() {
+        // Corporate author (collective name)
+        authors = all(record, &["CN"]).iter().map(|c| Name::literal(c)).collect();
+    }
+
+    let mut editors: Vec<Name> = all(record, &["FED"]).iter().map(|e| parse_name(e)).collect();
+    if editors.is_empty() {
+        editors = all(record, &["ED"]).iter().map(|e| parse_name(&expand_initials(e))).collect();
+    }
+
+    let (year, month) = first(record, &["DP", "DEP"]).map_or((None, None), |d| parse_date(&d));
+
+    // --- Venue ------------------------------------------------------------
+    let mut venue = Venue {
+        volume: first(record, &["VI"]),
+        issue: first(record, &["IP"]),
+        pages: first(record, &["PG"]).map(|p| expand_pages(&p)),
+        publisher: first(record, &["PB"]),
+        address: first(record, &["PL"]).filter(|_| kind != RefKind::Article),
+        ..Venue::default()
+    };
+
+    let mut extra = BTreeMap::new();
+
+    match kind {
+        RefKind::Article => {
+            venue.journal = first(record, &["JT", "TA"]);
+
+            if let (Some(_), Some(abbr)) = (first(record, &["JT"]), first(record, &["TA"])) {
+                extra.insert("shortjournal".to_string(), abbr);
+            }
+        }
+        RefKind::InCollection => venue.booktitle = book_title,
+        _ => {}
+    }
+
+    // --- Identifiers ------------------------------------------------------
+    let doi = all(record, &["LID", "AID"])
+        .iter()
+        .find_map(|v| v.strip_suffix("[doi]").map(|d| d.trim().to_string()));
+
+    let isbn = first(record, &["ISBN"]);
+
+    let identifiers = Identifiers {
+        doi,
+        isbn,
+        pmid: first(record, &["PMID"]),
+        pmcid: first(record, &["PMC"]),
+        ..Identifiers::default()
+    };
+
+    // --- Extra ------------------------------------------------------------
+    if let Some(ab) = first(record, &["AB"]) {
+        extra.insert("abstract".to_string(), ab);
+    }
+
+    let keywords = all(record, &["OT"]);
+    if !keywords.is_empty() {
+        extra.insert("keywords".to_string(), keywords.join(", "));
+    }
+
+    // `1234-5678 (Electronic)` → `1234-5678`
+    if let Some(issn) = first(record, &["IS"]) {
+        let issn = issn.split(" (").next().unwrap_or(&issn).trim().to_string();
+        extra.insert("issn".to_string(), issn);
+    }
+
+    if let Some(lang) = first(record, &["LA"]) {
+        extra.insert("language".to_string(), lang);
+    }
+
+    if let Some(name) = month.and_then(month_name) {
+        extra.insert("month".to_string(), name.to_string());
+    }
+
+    let mut reference = Reference {
+        id: String::new(),
+        sid: make_sid(),
+        kind,
+        title,
+        authors,
+        editors,
+        year,
+        identifiers,
+        venue: Some(venue),
+        tags: Vec::new(),
+        notes: None,
+        attachments: Vec::new(),
+        extra,
+        aliases: Vec::new(),
+    };
+
+    reference.id = make_key(&reference);
+
+    Some(reference)
+}
+
+/// `AU` names are `Smith JA`; rewrite as `Smith, J. A.`
+fn expand_initials(name: &str) -> String {
+    match name.rsplit_once(' ') {
+        Some((family, initials))
+            if initials.len() <= 3 && initials.chars().all(|c| c.is_ascii_uppercase()) =>
+        {
+            let given: Vec<String> = initials.chars().map(|c| format!("{}.", c)).collect();
+            format!("{}, {}", family, given.join(" "))
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// MEDLINE abbreviates end pages: `1021-9` → `1021--1029`
+fn expand_pages(pages: &str) -> String {
+    let Some((start, end)) = pages.split_once('-') else {
+        return pages.to_string();
+    };
+
+    let (start, end) = (start.trim(), end.trim());
+
+    let all_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+
+    if all_digits(start) && all_digits(end) && end.len() < start.len() {
+        let prefix = &start[..start.len() - end.len()];
+        return format!("{}--{}{}", start, prefix, end);
+    }
+
+    format!("{}--{}", start, end)
+}
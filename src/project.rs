@@ -7,6 +7,14 @@ pub struct Project {
     pub sid: String,
     pub title: Option<String>,
     pub refs: Vec<String>,
+
+    /// `@preamble` blocks imported into this library, re-emitted by `eln printed`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preambles: Vec<String>,
+
+    /// Where `eln printed` writes this library's `.bib` (default `<id>_references.bib`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
 }
 
 impl Project {
@@ -16,6 +24,8 @@ impl Project {
             sid,
             title: None,
             refs: Vec::new(),
+            preambles: Vec::new(),
+            output: None,
         }
     }
 }
@@ -40,18 +40,17 @@ pub fn load_all_projects() -> Vec<Project> {
 
     if let Ok(entries) = fs::read_dir(projects_dir()) {
         for e in entries.flatten() {
-            if let Ok(s) = fs::read_to_string(e.path()) {
-                if let Ok(mut p) = serde_yaml::from_str::<Project>(&s) {
-                    // 🔥 migrate here too
-                    if p.sid.len() < 16 {
-                        p.sid = make_sid();
-                        let _ = fs::write(
-                            e.path(),
-                            serde_yaml::to_string(&p).unwrap(),
-                        );
-                    }
-                    out.push(p);
+            if let Ok(s) = fs::read_to_string(e.path())
+                && let Ok(mut p) = serde_yaml::from_str::<Project>(&s) {
+                // 🔥 migrate here too
+                if p.sid.len() < 16 {
+                    p.sid = make_sid();
+                    let _ = fs::write(
+                        e.path(),
+                        serde_yaml::to_string(&p).unwrap(),
+                    );
                 }
+                out.push(p);
             }
         }
     }
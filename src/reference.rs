@@ -1,5 +1,10 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::bibtex::latex::ascii_fold;
+use crate::bibtex::names::Name;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Reference {
     pub id: String,
@@ -9,8 +14,8 @@ pub struct Reference {
 
     pub kind: RefKind,
     pub title: String,
-    pub authors: Vec<String>,
-    pub editors: Vec<String>,
+    pub authors: Vec<Name>,
+    pub editors: Vec<Name>,
     pub year: Option<u16>,
     pub identifiers: Identifiers,
     pub venue: Option<Venue>,
@@ -19,10 +24,41 @@ pub struct Reference {
 
     #[serde(default)]
     pub attachments: Vec<String>,
+
+    /// Unrecognised BibTeX fields, kept verbatim (keys lowercased).
+    /// A `BTreeMap` so that rendering order is deterministic.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, String>,
+
+    /// Former IDs (renamed keys, merged duplicates) that still resolve
+    /// to this reference
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
 }
 
+impl Reference {
+    /// True if `other` describes the same work: equal DOIs when both
+    /// have one, otherwise equal titles (ignoring case, accents and
+    /// punctuation).
+    pub fn is_same_work(&self, other: &Reference) -> bool {
+        if let (Some(a), Some(b)) = (&self.identifiers.doi, &other.identifiers.doi) {
+            return a.trim().eq_ignore_ascii_case(b.trim());
+        }
 
-#[derive(Debug, Serialize, Deserialize)]
+        normalize_title(&self.title) == normalize_title(&other.title)
+    }
+}
+
+fn normalize_title(title: &str) -> String {
+    ascii_fold(title)
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RefKind {
     Article,
     InProceedings,
@@ -30,6 +66,77 @@ pub enum RefKind {
     InBook,
     Book,
     Misc,
+    PhdThesis,
+    MastersThesis,
+    TechReport,
+    Manual,
+    Unpublished,
+    Proceedings,
+    Booklet,
+    Online,
+    Dataset,
+    Software,
+    Patent,
+}
+
+impl RefKind {
+    /// Parse a BibTeX/BibLaTeX entry type (case-insensitive).
+    /// Common aliases (`conference`, `report`, `www`, ...) are accepted.
+    pub fn from_bibtex(name: &str) -> Option<RefKind> {
+        use RefKind::*;
+
+        let kind = match name.to_lowercase().as_str() {
+            "article" => Article,
+            "inproceedings" | "conference" => InProceedings,
+            "incollection" => InCollection,
+            "inbook" => InBook,
+            "book" => Book,
+            "misc" => Misc,
+            "phdthesis" | "thesis" => PhdThesis,
+            "mastersthesis" => MastersThesis,
+            "techreport" | "report" => TechReport,
+            "manual" => Manual,
+            "unpublished" => Unpublished,
+            "proceedings" => Proceedings,
+            "booklet" => Booklet,
+            "online" | "electronic" | "www" | "webpage" => Online,
+            "dataset" => Dataset,
+            "software" => Software,
+            "patent" => Patent,
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+
+    /// Canonical lowercase entry type name.
+    pub fn bibtex_name(&self) -> &'static str {
+        use RefKind::*;
+
+        match self {
+            Article => "article",
+            InProceedings => "inproceedings",
+            InCollection => "incollection",
+            InBook => "inbook",
+            Book => "book",
+            Misc => "misc",
+            PhdThesis => "phdthesis",
+            MastersThesis => "mastersthesis",
+            TechReport => "techreport",
+            Manual => "manual",
+            Unpublished => "unpublished",
+            Proceedings => "proceedings",
+            Booklet => "booklet",
+            Online => "online",
+            Dataset => "dataset",
+            Software => "software",
+            Patent => "patent",
+        }
+    }
+
+    pub fn is_thesis(&self) -> bool {
+        matches!(self, RefKind::PhdThesis | RefKind::MastersThesis)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -38,9 +145,21 @@ pub struct Identifiers {
     pub arxiv: Option<String>,
     pub isbn: Option<String>,
     pub url: Option<String>,
+
+    /// arXiv primary category, e.g. `hep-th` or `cs.CL`
+    #[serde(default)]
+    pub arxiv_class: Option<String>,
+
+    /// PubMed ID, e.g. `31452104`
+    #[serde(default)]
+    pub pmid: Option<String>,
+
+    /// PubMed Central ID, e.g. `PMC6716357`
+    #[serde(default)]
+    pub pmcid: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Venue {
     pub journal: Option<String>,
     pub booktitle: Option<String>,
@@ -50,4 +169,39 @@ pub struct Venue {
     pub issue: Option<String>,
     pub pages: Option<String>,
     pub address: Option<String>,
+
+    /// Degree-granting institution (theses)
+    #[serde(default)]
+    pub school: Option<String>,
+
+    /// Publishing institution (technical reports)
+    #[serde(default)]
+    pub institution: Option<String>,
+
+    /// BibTeX `type` field, e.g. "PhD dissertation" or "Working paper"
+    #[serde(default, rename = "type")]
+    pub entry_type: Option<String>,
+}
+
+impl Venue {
+    /// Place a container title (journal, book or proceedings) in the
+    /// field BibTeX expects for `kind`.
+    pub fn set_container(&mut self, kind: RefKind, container: Option<String>) {
+        match kind {
+            RefKind::Article => self.journal = container,
+            _ => self.booktitle = container,
+        }
+    }
+
+    /// Place a publisher in the field BibTeX expects for `kind`: theses
+    /// and reports name their institution as the publisher.
+    pub fn set_publisher(&mut self, kind: RefKind, publisher: Option<String>) {
+        if kind.is_thesis() {
+            self.school = publisher;
+        } else if kind == RefKind::TechReport {
+            self.institution = publisher;
+        } else {
+            self.publisher = publisher;
+        }
+    }
 }
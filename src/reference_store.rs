@@ -4,7 +4,7 @@ use colored::*;
 use std::io::{self, Write};
 
 use crate::reference::Reference;
-use crate::utils::id::make_sid;
+use crate::utils::id::{disambiguate, make_sid};
 
 pub fn refs_dir() -> PathBuf {
     Path::new(".elaine").join("refs")
@@ -37,7 +37,30 @@ pub fn save_ref(reference: &Reference) {
         .expect("❌ Failed to write reference file");
 }
 
-pub fn create_or_update_ref(reference: Reference) {
+/// Store `reference`, returning the ID it ended up under.
+///
+/// If the ID is taken by a different work (see `Reference::is_same_work`)
+/// the new reference gets the first free `a`, `b`, ... suffix instead of
+/// overwriting it. The same work under the same ID asks before updating.
+pub fn create_or_update_ref(mut reference: Reference) -> String {
+    let base = reference.id.clone();
+
+    reference.id = disambiguate(&base, |id| {
+        ref_path(id).exists()
+            && !load_ref(id).is_some_and(|existing| existing.is_same_work(&reference))
+    });
+
+    if reference.id != base {
+        println!(
+            "{}",
+            format!(
+                "🔀 '{}' is taken by a different work; using '{}'",
+                base, reference.id
+            )
+            .bright_yellow()
+        );
+    }
+
     let path = ref_path(&reference.id);
 
     if path.exists() {
@@ -59,7 +82,7 @@ pub fn create_or_update_ref(reference: Reference) {
                 "{}",
                 format!("❌ Skipped reference '{}'", reference.id).red()
             );
-            return;
+            return reference.id;
         }
 
         save_ref(&reference);
@@ -78,6 +101,14 @@ pub fn create_or_update_ref(reference: Reference) {
                 .bold()
         );
     }
+
+    reference.id
+}
+
+/// `base`, or the first `basea`, `baseb`, ... not used by another atom.
+/// `own` is the reference's current ID, which never counts as taken.
+pub fn available_ref_id(base: &str, own: &str) -> String {
+    disambiguate(base, |id| id != own && ref_path(id).exists())
 }
 
 pub fn load_all_refs() -> Vec<Reference> {
@@ -90,15 +121,14 @@ pub fn load_all_refs() -> Vec<Reference> {
                 continue;
             }
 
-            if let Ok(contents) = fs::read_to_string(&path) {
-                if let Ok(mut r) = serde_yaml::from_str::<Reference>(&contents) {
-                    // migrate here too
-                    if r.sid.len() < 16 {
-                        r.sid = make_sid();
-                        let _ = fs::write(&path, serde_yaml::to_string(&r).unwrap());
-                    }
-                    refs.push(r);
+            if let Ok(contents) = fs::read_to_string(&path)
+                && let Ok(mut r) = serde_yaml::from_str::<Reference>(&contents) {
+                // migrate here too
+                if r.sid.len() < 16 {
+                    r.sid = make_sid();
+                    let _ = fs::write(&path, serde_yaml::to_string(&r).unwrap());
                 }
+                refs.push(r);
             }
         }
     }
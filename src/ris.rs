@@ -0,0 +1,382 @@
+//! RIS (Research Information Systems) import and export.
+//!
+//! RIS is the tagged line format offered by most publisher sites and
+//! databases (Web of Science, Scopus, IEEE Xplore):
+//!
+//! ```text
+//! TY  - JOUR
+//! AU  - Smith, John
+//! TI  - A Study
+//! ER  -
+//! ```
+
+use std::collections::BTreeMap;
+
+use colored::*;
+
+use crate::bibtex::latex::strip_case_braces;
+use crate::bibtex::{month_name, month_number};
+use crate::bibtex::names::{parse_name, Name};
+use crate::reference::{Identifiers, RefKind, Reference, Venue};
+use crate::utils::citekey::{imported_key, make_key};
+use crate::utils::id::make_sid;
+use crate::utils::normalize::{parse_date, strip_doi_prefix};
+
+// ============================================================
+// IMPORT
+// ============================================================
+
+/// Parse every `TY ... ER` record in `input`.
+/// Records without a title are skipped with a warning.
+pub fn parse_ris(input: &str) -> Vec<Reference> {
+    let mut refs = Vec::new();
+    let mut record: Vec<(String, String)> = Vec::new();
+    let mut in_record = false;
+
+    for line in input.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim_end();
+
+        let Some((tag, value)) = split_tag(line) else {
+            // Continuation of a wrapped value (abstracts, long titles)
+            if in_record
+                && !line.trim().is_empty()
+                && let Some((_, last)) = record.last_mut()
+            {
+                last.push(' ');
+                last.push_str(line.trim());
+            }
+            continue;
+        };
+
+        match tag {
+            "TY" => {
+                record.clear();
+                record.push((tag.to_string(), value.to_string()));
+                in_record = true;
+            }
+            "ER" if in_record => {
+                match record_to_reference(&record) {
+                    Some(r) => refs.push(r),
+                    None => eprintln!(
+                        "{}",
+                        format!(
+                            "⚠️  Skipping RIS record #{} (missing title)",
+                            refs.len() + 1
+                        )
+                        .yellow()
+                    ),
+                }
+                in_record = false;
+            }
+            _ if in_record => record.push((tag.to_string(), value.to_string())),
+            _ => {}
+        }
+    }
+
+    if in_record {
+        eprintln!("{}", "⚠️  Last RIS record is missing its ER line".yellow());
+        if let Some(r) = record_to_reference(&record) {
+            refs.push(r);
+        }
+    }
+
+    refs
+}
+
+/// `AU  - Smith, John` → (`AU`, `Smith, John`)
+fn split_tag(line: &str) -> Option<(&str, &str)> {
+    let tag = line.get(..2)?;
+    let rest = line.get(2..)?;
+
+    if !tag.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        || !tag.starts_with(|c: char| c.is_ascii_uppercase())
+    {
+        return None;
+    }
+
+    let value = rest.trim_start_matches(' ').strip_prefix('-')?;
+    Some((tag, value.trim()))
+}
+
+fn record_to_reference(record: &[(String, String)]) -> Option<Reference> {
+    let all = |tags: &[&str]| -> Vec<String> {
+        record
+            .iter()
+            .filter(|(t, v)| tags.contains(&t.as_str()) && !v.is_empty())
+            .map(|(_, v)| v.clone())
+            .collect()
+    };
+    let first = |tags: &[&str]| -> Option<String> {
+        // Respect tag priority, not record order
+        tags.iter().find_map(|t| all(&[*t]).into_iter().next())
+    };
+
+    let title = first(&["TI", "T1", "CT", "BT"])?;
+    let ty = first(&["TY"]).unwrap_or_default();
+    let kind = kind_from_ris(&ty);
+
+    let authors = ris_names(all(&["AU", "A1"]));
+    // A2 is the secondary author: editors everywhere except journals
+    let editors = ris_names(match kind {
+        RefKind::Article => all(&["ED"]),
+        _ => all(&["ED", "A2"]),
+    });
+
+    let (year, month) = first(&["PY", "Y1", "DA"]).map_or((None, None), |d| parse_date(&d));
+    let month = month.or_else(|| first(&["DA"]).and_then(|d| parse_date(&d).1));
+
+    // --- Venue ------------------------------------------------------------
+    let container = first(&["T2", "JO", "JF", "JA", "J2"]);
+
+    let pages = match (first(&["SP"]), first(&["EP"])) {
+        (Some(sp), Some(ep)) => Some(format!("{}--{}", sp, ep)),
+        (Some(sp), None) => Some(sp),
+        _ => None,
+    };
+
+    let publisher = first(&["PB"]);
+
+    let mut venue = Venue {
+        series: first(&["T3"]),
+        volume: first(&["VL"]),
+        issue: first(&["IS", "M1"]),
+        pages,
+        address: first(&["CY"]),
+        entry_type: first(&["M3"]),
+        ..Venue::default()
+    };
+
+    venue.set_container(kind, container);
+    venue.set_publisher(kind, publisher);
+
+    // --- Identifiers ------------------------------------------------------
+    let mut extra = BTreeMap::new();
+
+    // SN carries the ISBN for books and chapters, the ISSN otherwise
+    let sn = first(&["SN"]);
+    let isbn = match kind {
+        RefKind::Book | RefKind::InBook | RefKind::InCollection | RefKind::Proceedings => sn,
+        _ => {
+            if let Some(issn) = sn {
+                extra.insert("issn".to_string(), issn);
+            }
+            None
+        }
+    };
+
+    let identifiers = Identifiers {
+        doi: first(&["DO"]).map(|d| strip_doi_prefix(&d)),
+        isbn,
+        url: first(&["UR", "L2"]),
+        ..Identifiers::default()
+    };
+
+    // --- Extra ------------------------------------------------------------
+    let keywords = all(&["KW"]);
+    if !keywords.is_empty() {
+        extra.insert("keywords".to_string(), keywords.join(", "));
+    }
+
+    if let Some(ab) = first(&["AB", "N2"]) {
+        extra.insert("abstract".to_string(), ab);
+    }
+
+    if let Some(note) = first(&["N1"]) {
+        extra.insert("note".to_string(), note);
+    }
+
+    if let Some(name) = month.and_then(month_name) {
+        extra.insert("month".to_string(), name.to_string());
+    }
+
+    let mut reference = Reference {
+        id: String::new(),
+        sid: make_sid(),
+        kind,
+        title,
+        authors,
+        editors,
+        year,
+        identifiers,
+        venue: Some(venue),
+        tags: Vec::new(),
+        notes: None,
+        attachments: Vec::new(),
+        extra,
+        aliases: Vec::new(),
+    };
+
+    // EndNote and reference managers put record numbers in `ID`
+    reference.id = first(&["ID"])
+        .and_then(|id| imported_key(&id))
+        .unwrap_or_else(|| make_key(&reference));
+
+    Some(reference)
+}
+
+/// RIS names are `Last, First, Suffix`, while BibTeX puts the suffix
+/// second: reorder before parsing.
+fn ris_names(values: Vec<String>) -> Vec<Name> {
+    values
+        .iter()
+        .map(|v| match v.split(',').map(str::trim).collect::<Vec<_>>().as_slice() {
+            [family, given, suffix] => parse_name(&format!("{}, {}, {}", family, suffix, given)),
+            _ => parse_name(v),
+        })
+        .collect()
+}
+
+/// Inverse of [`ris_names`]
+fn ris_name(name: &Name) -> String {
+    if name.literal {
+        return name.family.clone();
+    }
+
+    let family = match &name.von {
+        Some(von) => format!("{} {}", von, name.family),
+        None => name.family.clone(),
+    };
+
+    match (&name.given, &name.jr) {
+        (Some(g), Some(jr)) => format!("{}, {}, {}", family, g, jr),
+        (Some(g), None) => format!("{}, {}", family, g),
+        (None, Some(jr)) => format!("{}, , {}", family, jr),
+        (None, None) => family,
+    }
+}
+
+fn kind_from_ris(ty: &str) -> RefKind {
+    match ty.trim().to_uppercase().as_str() {
+        "JOUR" | "JFULL" | "EJOUR" | "MGZN" | "NEWS" | "INPR" => RefKind::Article,
+        "BOOK" | "EBOOK" | "EDBOOK" => RefKind::Book,
+        "CHAP" | "ECHAP" => RefKind::InCollection,
+        "CONF" | "CPAPER" => RefKind::InProceedings,
+        "THES" => RefKind::PhdThesis,
+        "RPRT" => RefKind::TechReport,
+        "UNPB" | "MANSCPT" => RefKind::Unpublished,
+        "ELEC" | "WEB" | "BLOG" => RefKind::Online,
+        "DATA" | "DBASE" => RefKind::Dataset,
+        "COMP" => RefKind::Software,
+        "PAT" => RefKind::Patent,
+        "STAND" => RefKind::Manual,
+        "PAMP" => RefKind::Booklet,
+        _ => RefKind::Misc,
+    }
+}
+
+// ============================================================
+// EXPORT
+// ============================================================
+
+pub fn render_ris(refs: &[Reference]) -> String {
+    refs.iter().map(render_record).collect::<Vec<_>>().join("\n")
+}
+
+fn render_record(r: &Reference) -> String {
+    let mut out = String::new();
+
+    let mut tag = |name: &str, value: Option<&str>| {
+        if let Some(v) = value.filter(|v| !v.trim().is_empty()) {
+            out.push_str(&format!("{}  - {}\n", name, v));
+        }
+    };
+
+    tag("TY", Some(ris_type(r.kind)));
+    tag("ID", Some(&r.id));
+    // RIS has no case protection: `{DNA}` would show its braces
+    tag("TI", Some(&strip_case_braces(&r.title)));
+
+    for a in &r.authors {
+        tag("AU", Some(&ris_name(a)));
+    }
+    for e in &r.editors {
+        tag("ED", Some(&ris_name(e)));
+    }
+
+    if let Some(y) = r.year {
+        tag("PY", Some(&y.to_string()));
+
+        if let Some(m) = r.extra.get("month").and_then(|m| month_number(m)) {
+            tag("DA", Some(&format!("{}/{:02}//", y, m)));
+        }
+    }
+
+    // --- Venue ------------------------------------------------------------
+    if let Some(v) = &r.venue {
+        tag(
+            "T2",
+            v.journal
+                .as_deref()
+                .or(v.booktitle.as_deref())
+                .map(strip_case_braces)
+                .as_deref(),
+        );
+        tag("T3", v.series.as_deref().map(strip_case_braces).as_deref());
+        tag("VL", v.volume.as_deref());
+        tag("IS", v.issue.as_deref());
+
+        if let Some(p) = v.pages.as_deref() {
+            match p.split_once('-') {
+                Some((sp, ep)) => {
+                    tag("SP", Some(sp.trim()));
+                    tag("EP", Some(ep.trim_start_matches('-').trim()));
+                }
+                None => tag("SP", Some(p)),
+            }
+        }
+
+        tag(
+            "PB",
+            v.publisher
+                .as_deref()
+                .or(v.school.as_deref())
+                .or(v.institution.as_deref()),
+        );
+        tag("CY", v.address.as_deref());
+        tag("M3", v.entry_type.as_deref());
+    }
+
+    // --- Identifiers ------------------------------------------------------
+    tag("DO", r.identifiers.doi.as_deref());
+    tag(
+        "SN",
+        r.identifiers
+            .isbn
+            .as_deref()
+            .or(r.extra.get("issn").map(String::as_str)),
+    );
+    tag("UR", r.identifiers.url.as_deref());
+
+    // --- Extra ------------------------------------------------------------
+    if let Some(kw) = r.extra.get("keywords") {
+        for k in kw.split([',', ';']) {
+            tag("KW", Some(k.trim()));
+        }
+    }
+
+    tag("AB", r.extra.get("abstract").map(String::as_str));
+    tag("N1", r.extra.get("note").map(String::as_str));
+
+    out.push_str("ER  - \n");
+    out
+}
+
+fn ris_type(kind: RefKind) -> &'static str {
+    match kind {
+        RefKind::Article => "JOUR",
+        RefKind::Book => "BOOK",
+        RefKind::InBook | RefKind::InCollection => "CHAP",
+        RefKind::InProceedings => "CPAPER",
+        RefKind::Proceedings => "CONF",
+        RefKind::PhdThesis | RefKind::MastersThesis => "THES",
+        RefKind::TechReport => "RPRT",
+        RefKind::Manual => "STAND",
+        RefKind::Unpublished => "UNPB",
+        RefKind::Booklet => "PAMP",
+        RefKind::Online => "ELEC",
+        RefKind::Dataset => "DATA",
+        RefKind::Software => "COMP",
+        RefKind::Patent => "PAT",
+        RefKind::Misc => "GEN",
+    }
+}
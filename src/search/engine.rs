@@ -10,21 +10,25 @@ pub struct SearchResult {
 pub fn search_reference(r: &Reference) -> Vec<SearchResult> {
     let mut out = Vec::new();
 
-    // 1. DOI (hard stop)
+    // 1. DOI (hard stop, alongside any PubMed records)
     if let Some(res) = doi::search(r) {
         out.push(res);
+        out.extend(pubmed::search(r));
         return out;
     }
 
-    // 2. Explicit URL
+    // 2. PubMed / PubMed Central
+    out.extend(pubmed::search(r));
+
+    // 3. Explicit URL
     if let Some(res) = url::search(r) {
         out.push(res);
     }
 
-    // 3. Google Scholar
+    // 4. Google Scholar
     out.extend(google_scholar::search(r));
 
-    // 4. Fallback web search
+    // 5. Fallback web search
     if out.is_empty() {
         out.extend(web::search(r));
     }
@@ -6,8 +6,8 @@ pub fn search(r: &Reference) -> Vec<SearchResult> {
 
     parts.push(format!("\"{}\"", r.title));
 
-    if let Some(a) = r.authors.get(0) {
-        parts.push(a.clone());
+    if let Some(a) = r.authors.first() {
+        parts.push(a.display());
     }
 
     if let Some(y) = r.year {
@@ -1,4 +1,5 @@
 pub mod doi;
 pub mod url;
+pub mod pubmed;
 pub mod google_scholar;
 pub mod web;
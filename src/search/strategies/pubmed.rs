@@ -0,0 +1,24 @@
+use crate::reference::Reference;
+use crate::search::engine::SearchResult;
+
+pub fn search(r: &Reference) -> Vec<SearchResult> {
+    let mut out = Vec::new();
+
+    if let Some(pmid) = &r.identifiers.pmid {
+        out.push(SearchResult {
+            label: "PubMed".into(),
+            url: format!("https://pubmed.ncbi.nlm.nih.gov/{}/", pmid),
+            confidence: 0.95,
+        });
+    }
+
+    if let Some(pmcid) = &r.identifiers.pmcid {
+        out.push(SearchResult {
+            label: "PubMed Central".into(),
+            url: format!("https://www.ncbi.nlm.nih.gov/pmc/articles/{}/", pmcid),
+            confidence: 0.95,
+        });
+    }
+
+    out
+}
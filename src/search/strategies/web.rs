@@ -5,7 +5,7 @@ pub fn search(r: &Reference) -> Vec<SearchResult> {
     let q = format!(
         "\"{}\" \"{}\" {}",
         r.title,
-        r.authors.get(0).unwrap_or(&"".into()),
+        r.authors.first().map(|a| a.display()).unwrap_or_default(),
         r.year.unwrap_or_default()
     );
 
@@ -0,0 +1,240 @@
+//! Citation key templates (`citekey.format` in `.elaine/config.yaml`).
+//!
+//! A template is literal text with `{field:modifier:...}` placeholders:
+//!
+//! ```text
+//! {auth}{year}{title:w1:capitalize}   →  Smith2020Deep
+//! {auth:lower:5}{year}{title:lower:6} →  smith2020deeple  (built-in scheme)
+//! {authors:w2}_{year}                 →  SmithJones_2020
+//! ```
+//!
+//! Fields produce a list of words: `auth` (first author's family name),
+//! `authors` (every family name), `year`, `title` and `journal`.
+//! Modifiers apply left to right: `wN` keeps the first N words, `N`
+//! truncates to N characters, and `lower` / `upper` / `capitalize` change
+//! case. Field values are reduced to letters and digits; literal text is
+//! copied as-is.
+
+use colored::*;
+
+use crate::bibtex::latex::ascii_fold;
+use crate::config::{load_config, CitekeyConfig};
+use crate::reference::Reference;
+use crate::utils::id::make_ref_id;
+
+#[derive(Debug)]
+enum Segment {
+    Literal(String),
+    Field { name: Field, modifiers: Vec<Modifier> },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Auth,
+    Authors,
+    Year,
+    Title,
+    Journal,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Modifier {
+    Words(usize),
+    Chars(usize),
+    Lower,
+    Upper,
+    Capitalize,
+}
+
+/// Key for `r` under the configured template, or the built-in scheme
+/// when none is set. An invalid template is reported and ignored.
+pub fn make_key(r: &Reference) -> String {
+    let config = load_config().citekey;
+
+    match render_key(r, &config) {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                format!("⚠️  Invalid citekey.format in .elaine/config.yaml: {}", e).yellow()
+            );
+            make_ref_id(&r.authors, r.year, &r.title)
+        }
+    }
+}
+
+/// `id` from an imported file (CSL `id`, RIS `ID`) as a reference ID,
+/// if it looks like a citation key. Record numbers (`42`), URLs such as
+/// Zotero's item URIs and text with spaces yield `None`, so the caller
+/// falls back to [`make_key`].
+pub fn imported_key(id: &str) -> Option<String> {
+    let id = id.trim();
+
+    let is_url = id.contains("://") || id.starts_with("www.");
+    let is_number = id.chars().all(|c| c.is_ascii_digit());
+
+    if id.is_empty() || is_url || is_number || id.chars().any(char::is_whitespace) {
+        return None;
+    }
+
+    Some(id.replace(['/', ':'], "_"))
+}
+
+/// Render `r` with `config`. Errors describe a malformed template.
+pub fn render_key(r: &Reference, config: &CitekeyConfig) -> Result<String, String> {
+    let Some(format) = config.format.as_deref() else {
+        return Ok(make_ref_id(&r.authors, r.year, &r.title));
+    };
+
+    let mut key = String::new();
+
+    for segment in parse_template(format)? {
+        match segment {
+            Segment::Literal(s) => key.push_str(&s),
+            Segment::Field { name, modifiers } => {
+                let mut words = field_words(r, name, config);
+
+                for m in modifiers {
+                    apply(&mut words, m);
+                }
+
+                key.push_str(&words.concat());
+            }
+        }
+    }
+
+    // Every placeholder came out empty: fall back rather than write `.yaml`
+    if key.is_empty() {
+        return Ok(make_ref_id(&r.authors, r.year, &r.title));
+    }
+
+    Ok(key)
+}
+
+/// Check a template without rendering it.
+pub fn validate_template(format: &str) -> Result<(), String> {
+    parse_template(format).map(|_| ())
+}
+
+fn parse_template(format: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut rest = format;
+
+    while let Some(open) = rest.find('{') {
+        if open > 0 {
+            segments.push(Segment::Literal(rest[..open].to_string()));
+        }
+
+        let close = rest[open..]
+            .find('}')
+            .map(|c| open + c)
+            .ok_or_else(|| format!("unclosed '{{' in '{}'", format))?;
+
+        let mut parts = rest[open + 1..close].split(':').map(str::trim);
+
+        let name = match parts.next().unwrap_or_default() {
+            "auth" => Field::Auth,
+            "authors" => Field::Authors,
+            "year" => Field::Year,
+            "title" => Field::Title,
+            "journal" => Field::Journal,
+            other => return Err(format!("unknown field '{}'", other)),
+        };
+
+        let modifiers = parts.map(parse_modifier).collect::<Result<_, _>>()?;
+
+        segments.push(Segment::Field { name, modifiers });
+        rest = &rest[close + 1..];
+    }
+
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest.to_string()));
+    }
+
+    Ok(segments)
+}
+
+fn parse_modifier(m: &str) -> Result<Modifier, String> {
+    let count = |s: &str| s.parse::<usize>().ok().filter(|n| *n > 0);
+
+    match m {
+        "lower" => Ok(Modifier::Lower),
+        "upper" => Ok(Modifier::Upper),
+        "capitalize" => Ok(Modifier::Capitalize),
+        _ => {
+            if let Some(n) = m.strip_prefix('w').and_then(count) {
+                Ok(Modifier::Words(n))
+            } else if let Some(n) = count(m) {
+                Ok(Modifier::Chars(n))
+            } else {
+                Err(format!("unknown modifier '{}'", m))
+            }
+        }
+    }
+}
+
+fn field_words(r: &Reference, field: Field, config: &CitekeyConfig) -> Vec<String> {
+    let family = |n: &crate::bibtex::names::Name| n.family_plain();
+
+    let raw: Vec<String> = match field {
+        Field::Auth => vec![
+            r.authors
+                .first()
+                .or(r.editors.first())
+                .map(family)
+                .unwrap_or_else(|| "unknown".to_string()),
+        ],
+        Field::Authors => {
+            let names = if r.authors.is_empty() { &r.editors } else { &r.authors };
+            names.iter().map(family).collect()
+        }
+        Field::Year => vec![format!("{:04}", r.year.unwrap_or(0))],
+        Field::Title => r
+            .title
+            .split_whitespace()
+            .filter(|w| {
+                !config
+                    .skip_words
+                    .iter()
+                    .any(|s| s.eq_ignore_ascii_case(&clean(w, false)))
+            })
+            .map(str::to_string)
+            .collect(),
+        Field::Journal => r
+            .venue
+            .as_ref()
+            .and_then(|v| v.journal.as_deref())
+            .map(|j| j.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default(),
+    };
+
+    raw.iter()
+        .map(|w| clean(w, config.transliterate))
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Letters and digits only, optionally folded to ASCII.
+fn clean(word: &str, transliterate: bool) -> String {
+    let word = if transliterate { ascii_fold(word) } else { word.to_string() };
+    word.chars().filter(|c| c.is_alphanumeric()).collect()
+}
+
+fn apply(words: &mut Vec<String>, modifier: Modifier) {
+    match modifier {
+        Modifier::Words(n) => words.truncate(n),
+        Modifier::Chars(n) => {
+            let joined: String = words.concat().chars().take(n).collect();
+            *words = vec![joined];
+        }
+        Modifier::Lower => words.iter_mut().for_each(|w| *w = w.to_lowercase()),
+        Modifier::Upper => words.iter_mut().for_each(|w| *w = w.to_uppercase()),
+        Modifier::Capitalize => words.iter_mut().for_each(|w| {
+            let mut chars = w.chars();
+            *w = match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+                None => String::new(),
+            };
+        }),
+    }
+}
@@ -1,5 +1,8 @@
 use uuid::Uuid;
 
+use crate::bibtex::latex::ascii_fold;
+use crate::bibtex::names::Name;
+
 /// Generate a globally unique opaque SID (UUID v4).
 /// NEVER hash semantic identifiers again.
 pub fn make_sid() -> String {
@@ -11,24 +14,14 @@ pub fn sid_short(sid: &str) -> &str {
     if sid.len() >= 8 { &sid[..8] } else { sid }
 }
 
-pub fn make_ref_id(authors: &[String], year: Option<u16>, title: &str) -> String {
+/// `<family:5><year:4><title:6>`, folded to ASCII so keys stay valid
+/// under classic BibTeX
+pub fn make_ref_id(authors: &[Name], year: Option<u16>, title: &str) -> String {
     let author_part: String = authors
-        .get(0)
+        .first()
         .map(|a| {
-            let last = if a.contains(',') {
-                // "Last, First"
-                a.split(',')
-                    .next()
-                    .unwrap()
-                    .trim()
-            } else {
-                // "First Last" → take last token
-                a.split_whitespace()
-                    .last()
-                    .unwrap_or(a)
-            };
-
-            last.to_lowercase()
+            ascii_fold(&a.family_plain())
+                .to_lowercase()
                 .chars()
                 .filter(|c| c.is_alphanumeric())
                 .take(5)
@@ -39,7 +32,7 @@ pub fn make_ref_id(authors: &[String], year: Option<u16>, title: &str) -> String
 
     let year_part = year.unwrap_or(0);
 
-    let title_part: String = title
+    let title_part: String = ascii_fold(title)
         .to_lowercase()
         .chars()
         .filter(|c| c.is_alphanumeric())
@@ -48,3 +41,33 @@ pub fn make_ref_id(authors: &[String], year: Option<u16>, title: &str) -> String
 
     format!("{}{:04}{}", author_part, year_part, title_part)
 }
+
+/// First of `base`, `basea`, `baseb`, ... for which `taken` is false.
+pub fn disambiguate(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(base) {
+        return base.to_string();
+    }
+
+    let mut n = 0usize;
+    loop {
+        let candidate = format!("{}{}", base, alpha_suffix(n));
+        if !taken(&candidate) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// 0 → `a`, 25 → `z`, 26 → `aa`, ...
+fn alpha_suffix(mut n: usize) -> String {
+    let mut out = Vec::new();
+    loop {
+        out.push(b'a' + (n % 26) as u8);
+        if n < 26 {
+            break;
+        }
+        n = n / 26 - 1;
+    }
+    out.reverse();
+    String::from_utf8(out).unwrap()
+}
@@ -1,3 +1,5 @@
+pub mod citekey;
 pub mod id;
+pub mod normalize;
 pub mod resolve;
 pub mod resolve_project;
@@ -0,0 +1,69 @@
+//! Field clean-up shared by the `eln add` file importers, `eln import` and
+//! `eln dedupe`.
+
+use crate::bibtex::month_number;
+
+/// `https://doi.org/10.1000/ABC` or `doi:10.1000/ABC` → `10.1000/ABC`
+pub fn strip_doi_prefix(doi: &str) -> String {
+    let doi = doi.trim();
+    let lower = doi.to_lowercase();
+
+    [
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "http://dx.doi.org/",
+        "doi:",
+    ]
+    .iter()
+    .find(|p| lower.starts_with(*p))
+    .map_or(doi, |p| doi[p.len()..].trim())
+    .to_string()
+}
+
+/// `1-10`, `1–10` or `1---10` → `1--10`
+pub fn normalize_pages(pages: &str) -> String {
+    match pages.split_once(['-', '–']) {
+        Some((a, b)) => format!("{}--{}", a.trim(), b.trim_start_matches(['-', '–']).trim()),
+        None => pages.to_string(),
+    }
+}
+
+/// `2020`, `2020-03-01`, `2020/03/15/`, `20190827`, `March 2020`,
+/// `1 March 2020` or `2019 Sep-Oct` → (year, month)
+pub fn parse_date(value: &str) -> (Option<u16>, Option<u8>) {
+    let value = value.trim();
+    let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+
+    // PubMed `DEP` dates are `20190827`
+    if value.len() == 8 && is_digits(value) {
+        return (
+            value[..4].parse().ok(),
+            value[4..6].parse().ok().filter(|m| (1..=12).contains(m)),
+        );
+    }
+
+    let tokens: Vec<&str> = value
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let year = tokens
+        .iter()
+        .find(|t| t.len() == 4 && is_digits(t))
+        .and_then(|t| t.parse().ok());
+
+    // Numeric months only directly after a leading year (`2020-03`);
+    // elsewhere a number is a day, never a month
+    let month = match tokens.as_slice() {
+        [y, m, ..] if y.len() == 4 && is_digits(y) && is_digits(m) => {
+            m.parse().ok().filter(|m| (1..=12).contains(m))
+        }
+        _ => tokens
+            .iter()
+            .filter(|t| !is_digits(t))
+            .find_map(|t| month_number(t)),
+    };
+
+    (year, month)
+}
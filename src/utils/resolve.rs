@@ -23,8 +23,9 @@ pub enum ResolveError {
 ///    - 0 matches → fall through
 ///    - >1 matches → ambiguity error
 /// 3. Exact ID match
-/// 4. Unique ID prefix
-/// 5. Not found
+/// 4. Alias match (former IDs of renamed or merged references)
+/// 5. Unique ID prefix
+/// 6. Not found
 ///
 /// NOTE:
 /// - Ambiguity is terminal
@@ -73,7 +74,13 @@ pub fn resolve_reference(selector: &str) -> Result<String, ResolveError> {
         return Ok(r.id.clone());
     }
 
-    // ---- 4. ID prefix match ----------------------------------------------
+    // ---- 4. Alias match -------------------------------------------------
+
+    if let Some(r) = refs.iter().find(|r| r.aliases.iter().any(|a| a == selector)) {
+        return Ok(r.id.clone());
+    }
+
+    // ---- 5. ID prefix match ----------------------------------------------
 
     let id_matches: Vec<&Reference> = by_id
         .iter()